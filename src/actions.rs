@@ -1,19 +1,66 @@
 use anyhow::{anyhow, Context, Result};
 use chrono::Local;
 use std::ffi::OsStr;
-use std::path::PathBuf;
+use std::path::Path;
 
 use crate::config::Config;
-use crate::domain::{parse_number, slugify, AdrMeta};
+use crate::document::{
+    history_section, history_yaml, yaml_list, yaml_refs, AdrDocument, HISTORY_SECTION, META_KEYS,
+};
+use crate::domain::{
+    link_targets, list_contains, parse_adr_ref, parse_list, parse_number, slugify, status_class,
+    AdrMeta, LinkKind, People, Role, StatusChange,
+};
+use crate::graph::{self, GraphFormat};
 use crate::repository::{idx_path, AdrRepository};
-use crate::yaml_util::escape_yaml;
+use crate::yaml_util::{escape_yaml, yaml_scalar};
+use serde_yaml::{Mapping, Value};
 use std::collections::HashMap;
 
+/// Section skeleton for new ADRs in the classic and front matter layouts.
+const SKELETON: &str = "## Context\n\nDescribe the context and forces at play.\n\n## Decision\n\nState the decision that was made and why.\n\n## Consequences\n\nList the trade-offs and follow-ups.\n";
+
+/// Section skeleton for new MADR-layout ADRs.
+const MADR_SKELETON: &str = "## Context and Problem Statement\n\nDescribe the context and problem statement.\n\n## Decision Drivers\n\n* Driver 1\n\n## Considered Options\n\n* Option 1\n* Option 2\n\n## Decision Outcome\n\nChosen option: \"Option 1\", because it best meets the decision drivers.\n\n## Pros and Cons of the Options\n\n### Option 1\n\n* Good, because ...\n* Bad, because ...\n\n### Option 2\n\n* Good, because ...\n* Bad, because ...\n";
+
+/// Front matter keys radr renders itself; any other key is carried over as is.
+const MANAGED_FM_KEYS: &[&str] = &[
+    "title",
+    "number",
+    "date",
+    "status",
+    "supersedes",
+    "superseded_by",
+    "deprecation_reason",
+    "amends",
+    "amended_by",
+    "relates_to",
+    "depends_on",
+    "required_by",
+    "conflicts_with",
+    "tags",
+    "authors",
+    "deciders",
+    "consulted",
+    "informed",
+    "history",
+];
+
+/// Metadata radr does not manage, carried over when an ADR is re-rendered: unknown
+/// front matter keys (in file order) and unknown `Key: value` header lines.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Extras {
+    pub front_matter: Mapping,
+    pub header_lines: Vec<String>,
+}
+
 pub fn create_new_adr<R: AdrRepository>(
     repo: &R,
     cfg: &Config,
     title: &str,
-    supersedes: Option<u32>,
+    supersedes: &[u32],
+    tags: &[String],
+    people: &People,
 ) -> Result<AdrMeta> {
     let mut adrs = repo.list()?;
     let next = adrs.iter().map(|a| a.number).max().unwrap_or(0) + 1;
@@ -23,23 +70,39 @@ pub fn create_new_adr<R: AdrRepository>(
     let path = repo.adr_dir().join(filename);
     let date = Local::now().format("%Y-%m-%d").to_string();
 
-    // Resolve supersedes display: link to existing ADR filename when possible
-    let supersedes_display = supersedes.map(|n| {
-        if let Some(fname) = adrs
-            .iter()
-            .find(|a| a.number == n)
-            .and_then(|a| a.path.file_name().and_then(OsStr::to_str))
-        {
-            format!("[{:04}]({})", n, fname)
-        } else {
-            format!("{:04}", n)
+    // Resolve supersedes display: link to existing ADR filenames when possible
+    let mut by_number: HashMap<u32, String> = HashMap::new();
+    for a in &adrs {
+        if let Some(fname) = a.path.file_name().and_then(OsStr::to_str) {
+            by_number.insert(a.number, fname.to_string());
         }
-    });
+    }
+    let supersedes_display = (!supersedes.is_empty()).then(|| refs_value(supersedes, &by_number));
+    let tags = parse_list(&tags.join(","));
+    let people = People::default().merged(people);
+
+    let meta = AdrMeta {
+        number: next,
+        title: title.to_string(),
+        status: "Proposed".to_string(),
+        date: date.clone(),
+        created: date.clone(),
+        decided: None,
+        history: Vec::new(),
+        supersedes: supersedes.to_vec(),
+        superseded_by: None,
+        deprecation_reason: None,
+        links: Vec::new(),
+        tags: tags.clone(),
+        people: people.clone(),
+        path: path.clone(),
+    };
 
     let content = if let Some(tpl_path) = &cfg.template {
         let tpl = std::fs::read_to_string(tpl_path)
             .with_context(|| format!("Reading template at {}", tpl_path.display()))?;
-        tpl.replace("{{NUMBER}}", &format!("{:04}", next))
+        let mut tpl = tpl
+            .replace("{{NUMBER}}", &format!("{:04}", next))
             .replace("{{TITLE}}", title)
             .replace("{{DATE}}", &date)
             .replace("{{STATUS}}", "Proposed")
@@ -47,20 +110,38 @@ pub fn create_new_adr<R: AdrRepository>(
                 "{{SUPERSEDES}}",
                 supersedes_display.as_deref().unwrap_or_default(),
             )
+            .replace("{{TAGS}}", &tags.join(", "));
+        for role in Role::ALL {
+            let placeholder = format!("{{{{{}}}}}", role.header_key().to_ascii_uppercase());
+            tpl = tpl.replace(&placeholder, &people.get(role).join(", "));
+        }
+        tpl
+    } else if cfg.is_madr() {
+        render_adr(
+            cfg,
+            &meta,
+            &HashMap::new(),
+            MADR_SKELETON,
+            &Extras::default(),
+        )
+    } else if cfg.full_front_matter() {
+        render_adr(cfg, &meta, &HashMap::new(), SKELETON, &Extras::default())
     } else if cfg.front_matter {
         let mut body = String::new();
         body.push_str("---\n");
         body.push_str(&format!("title: {}\n", escape_yaml(title)));
+        if !tags.is_empty() {
+            body.push_str(&format!("tags: {}\n", yaml_list(&tags)));
+        }
         body.push_str("---\n\n");
         body.push_str(&format!("Date: {}\n", date));
         body.push_str("Status: Proposed\n");
         if let Some(sup) = &supersedes_display {
             body.push_str(&format!("Supersedes: {}\n", sup));
         }
+        push_people_lines(&mut body, &people);
         body.push('\n');
-        body.push_str("## Context\n\nDescribe the context and forces at play.\n\n");
-        body.push_str("## Decision\n\nState the decision that was made and why.\n\n");
-        body.push_str("## Consequences\n\nList the trade-offs and follow-ups.\n");
+        body.push_str(SKELETON);
         body
     } else {
         let mut header = format!(
@@ -70,23 +151,17 @@ pub fn create_new_adr<R: AdrRepository>(
         if let Some(sup) = &supersedes_display {
             header.push_str(&format!("Supersedes: {}\n", sup));
         }
-        header.push_str(
-                "\n## Context\n\nDescribe the context and forces at play.\n\n## Decision\n\nState the decision that was made and why.\n\n## Consequences\n\nList the trade-offs and follow-ups.\n",
-            );
+        if !tags.is_empty() {
+            header.push_str(&format!("Tags: {}\n", tags.join(", ")));
+        }
+        push_people_lines(&mut header, &people);
+        header.push('\n');
+        header.push_str(SKELETON);
         header
     };
 
     repo.write_string(&path, &content)?;
 
-    let meta = AdrMeta {
-        number: next,
-        title: title.to_string(),
-        status: "Proposed".to_string(),
-        date,
-        supersedes,
-        superseded_by: None,
-        path: path.clone(),
-    };
     adrs.push(meta.clone());
     adrs.sort_by_key(|a| a.number);
     write_index(repo, cfg, &adrs)?;
@@ -100,131 +175,32 @@ pub fn mark_superseded<R: AdrRepository>(
     new_number: u32,
 ) -> Result<()> {
     // Locate ADR by listing metadata to be robust even if dir missing
-    let adrs = repo.list()?;
-    let path: PathBuf = adrs
+    let target = repo
+        .list()?
         .into_iter()
         .find(|a| a.number == old_number)
-        .map(|a| a.path)
         .ok_or_else(|| anyhow!("Could not find ADR {:04} to supersede", old_number))?;
 
-    let contents = repo.read_string(&path)?;
-    let mut updated = String::new();
-    if let Some(stripped) = contents.strip_prefix("---\n") {
-        // Front matter present: keep it as-is, update fields in body
-        if let Some(end) = stripped.find("\n---\n") {
-            let fm_block = &stripped[..end];
-            let rest = &stripped[end + 5..];
-            let mut lines: Vec<String> = rest.lines().map(|s| s.to_string()).collect();
-            // Update status/superseded-by with ordering
-            let mut idx_status: Option<usize> = None;
-            let mut idx_superseded_by: Option<usize> = None;
-            for (i, l) in lines.iter_mut().enumerate() {
-                if l.starts_with("Status:") {
-                    *l = format!("Status: Superseded by {:04}", new_number);
-                    idx_status = Some(i);
-                }
-                if l.starts_with("Superseded-by:") {
-                    *l = format!("Superseded-by: {:04}", new_number);
-                    idx_superseded_by = Some(i);
-                }
-            }
-            if idx_status.is_none() {
-                let insert_at = 0; // top of body
-                lines.insert(
-                    insert_at,
-                    format!("Status: Superseded by {:04}", new_number),
-                );
-                idx_status = Some(insert_at);
-            }
-            match (idx_status, idx_superseded_by) {
-                (Some(s_idx), Some(sb_idx)) => {
-                    let desired = s_idx + 1;
-                    if sb_idx != desired {
-                        let _ = lines.remove(sb_idx);
-                        let insert_pos = if sb_idx < desired {
-                            desired - 1
-                        } else {
-                            desired
-                        };
-                        lines.insert(insert_pos, format!("Superseded-by: {:04}", new_number));
-                    }
-                }
-                (Some(s_idx), None) => {
-                    lines.insert(s_idx + 1, format!("Superseded-by: {:04}", new_number));
-                }
-                _ => {}
-            }
-
-            updated.push_str("---\n");
-            updated.push_str(fm_block);
-            updated.push_str("\n---\n");
-            if !rest.starts_with('\n') && (lines.first().map(|l| !l.is_empty()).unwrap_or(false)) {
-                updated.push('\n');
-            }
-            updated.push_str(&lines.join("\n"));
-            if !updated.ends_with('\n') {
-                updated.push('\n');
-            }
-        } else {
-            updated = contents;
-        }
-    } else {
-        let mut lines: Vec<String> = contents.lines().map(|s| s.to_string()).collect();
-        let mut idx_status: Option<usize> = None;
-        let mut idx_superseded_by: Option<usize> = None;
-        for (i, l) in lines.iter_mut().enumerate() {
-            if l.starts_with("Status:") {
-                *l = format!("Status: Superseded by {:04}", new_number);
-                idx_status = Some(i);
-            }
-            if l.starts_with("Superseded-by:") {
-                *l = format!("Superseded-by: {:04}", new_number);
-                idx_superseded_by = Some(i);
-            }
-        }
-        if idx_status.is_none() {
-            let insert_at = if !lines.is_empty() { 1 } else { 0 };
-            lines.insert(
-                insert_at,
-                format!("Status: Superseded by {:04}", new_number),
-            );
-            idx_status = Some(insert_at);
-        }
-        // Ensure Superseded-by appears immediately after Status
-        match (idx_status, idx_superseded_by) {
-            (Some(s_idx), Some(sb_idx)) => {
-                let desired = s_idx + 1;
-                if sb_idx != desired {
-                    // Remove current and insert at desired (adjust if removing before desired)
-                    let _ = lines.remove(sb_idx);
-                    let insert_pos = if sb_idx < desired {
-                        desired - 1
-                    } else {
-                        desired
-                    };
-                    lines.insert(insert_pos, format!("Superseded-by: {:04}", new_number));
-                }
-            }
-            (Some(s_idx), None) => {
-                lines.insert(s_idx + 1, format!("Superseded-by: {:04}", new_number));
-            }
-            _ => {}
-        }
-
-        updated = lines.join("\n");
-        if !updated.ends_with('\n') {
-            updated.push('\n');
-        }
-    }
-    repo.write_string(&path, &updated)?;
-
-    // refresh index
-    let adrs = repo.list()?;
-    write_index(repo, cfg, &adrs)?;
+    let status = format!("Superseded by {:04}", new_number);
+    let today = Local::now().format("%Y-%m-%d").to_string();
+    edit_adr(repo, cfg, &target, |doc| {
+        doc.set_fields(&[
+            ("Status", status.clone()),
+            ("Superseded-by", format!("{:04}", new_number)),
+        ]);
+        record_status(doc, &target, &status, &today, None);
+    })?;
     Ok(())
 }
 
-pub fn reformat<R: AdrRepository>(repo: &R, cfg: &Config, id: u32) -> Result<AdrMeta> {
+/// Re-renders ADR `id` in the layout selected by `cfg`, renaming the file when the
+/// format changes. Returns the updated metadata plus warnings for anything that
+/// could not be carried over.
+pub fn reformat<R: AdrRepository>(
+    repo: &R,
+    cfg: &Config,
+    id: u32,
+) -> Result<(AdrMeta, Vec<String>)> {
     let adrs = repo.list()?;
     let target = adrs
         .iter()
@@ -241,82 +217,22 @@ pub fn reformat<R: AdrRepository>(repo: &R, cfg: &Config, id: u32) -> Result<Adr
         }
     }
 
-    // Extract body content after any header/front-matter + meta lines
-    fn body_after_meta(raw: &str) -> String {
-        let mut rest = raw;
-        if let Some(stripped) = raw.strip_prefix("---\n") {
-            if let Some(end) = stripped.find("\n---\n") {
-                rest = &stripped[end + 5..];
-            }
-        }
-        let lines: Vec<&str> = rest.lines().collect();
-        let mut i = 0usize;
-        if i < lines.len() && lines[i].starts_with("# ADR ") {
-            i += 1;
-            if i < lines.len() && lines[i].trim().is_empty() {
-                i += 1;
-            }
-        }
-        while i < lines.len() {
-            let l = lines[i];
-            let is_meta = l.starts_with("Title:")
-                || l.starts_with("Date:")
-                || l.starts_with("Status:")
-                || l.starts_with("Supersedes:")
-                || l.starts_with("Superseded-by:");
-            if is_meta || l.trim().is_empty() {
-                i += 1;
-                continue;
-            }
-            break;
-        }
-        let tail = lines[i..].join("\n");
-        if tail.is_empty() {
-            String::new()
-        } else {
-            format!("{}\n", tail)
+    let mut warnings = Vec::new();
+    let (extras, tail_body) = split_for_render(&original, &target.title, &mut warnings);
+    if !cfg.is_madr() && !cfg.front_matter {
+        for key in extras.front_matter.keys() {
+            warnings.push(format!(
+                "front matter key '{}' dropped: the classic layout has no front matter",
+                yaml_key_display(key)
+            ));
         }
     }
+    let warnings = warnings
+        .into_iter()
+        .map(|w| format!("{:04}: {}", id, w))
+        .collect();
 
-    let tail_body = body_after_meta(&original);
-
-    // Render new content according to cfg
-    let mut new_content = String::new();
-    if cfg.front_matter {
-        new_content.push_str("---\n");
-        new_content.push_str(&format!("title: {}\n", escape_yaml(&target.title)));
-        new_content.push_str("---\n\n");
-        new_content.push_str(&format!("Date: {}\n", target.date));
-        new_content.push_str(&format!("Status: {}\n", target.status));
-        if let Some(n) = target.superseded_by {
-            new_content.push_str(&format!("Superseded-by: {:04}\n", n));
-        }
-        if let Some(n) = target.supersedes {
-            if let Some(fname) = by_number.get(&n) {
-                new_content.push_str(&format!("Supersedes: [{:04}]({})\n", n, fname));
-            } else {
-                new_content.push_str(&format!("Supersedes: {:04}\n", n));
-            }
-        }
-        new_content.push('\n');
-        new_content.push_str(&tail_body);
-    } else {
-        new_content.push_str(&format!("# ADR {:04}: {}\n\n", target.number, target.title));
-        new_content.push_str(&format!("Date: {}\n", target.date));
-        new_content.push_str(&format!("Status: {}\n", target.status));
-        if let Some(n) = target.superseded_by {
-            new_content.push_str(&format!("Superseded-by: {:04}\n", n));
-        }
-        if let Some(n) = target.supersedes {
-            if let Some(fname) = by_number.get(&n) {
-                new_content.push_str(&format!("Supersedes: [{:04}]({})\n", n, fname));
-            } else {
-                new_content.push_str(&format!("Supersedes: {:04}\n", n));
-            }
-        }
-        new_content.push('\n');
-        new_content.push_str(&tail_body);
-    }
+    let new_content = render_adr(cfg, target, &by_number, &tail_body, &extras);
 
     // Determine new path
     let slug = slugify(&target.title);
@@ -331,42 +247,46 @@ pub fn reformat<R: AdrRepository>(repo: &R, cfg: &Config, id: u32) -> Result<Adr
         let _ = std::fs::remove_file(&target.path);
     }
 
-    // Update incoming links in other ADRs' Supersedes lines to point to the new filename
+    // Update incoming links in other ADRs' Supersedes and typed link lines to point
+    // to the new filename
     let new_filename = new_path
         .file_name()
         .and_then(OsStr::to_str)
         .unwrap_or("")
         .to_string();
-    let mut adrs_scan = repo.list()?;
-    for a in &mut adrs_scan {
+    let link_keys: Vec<&str> = std::iter::once("Supersedes")
+        .chain(LinkKind::ALL.iter().map(|k| k.header_key()))
+        .collect();
+    for a in repo.list()? {
         if a.number == id {
             continue;
         }
-        let content = repo.read_string(&a.path)?;
-        let mut changed = false;
-        let mut lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
-        for l in &mut lines {
-            if l.starts_with("Supersedes: [") {
-                // Try to parse number between [ and ]
-                if let Some(lb) = l.find('[') {
-                    if let Some(rb) = l[lb + 1..].find(']') {
-                        let num_str = &l[lb + 1..lb + 1 + rb];
-                        if let Ok(n) = num_str.parse::<u32>() {
-                            if n == id {
-                                *l = format!("Supersedes: [{:04}]({})", n, new_filename);
-                                changed = true;
-                            }
-                        }
+        let mut doc = AdrDocument::parse(&repo.read_string(&a.path)?);
+        let mut fields: Vec<(&str, String)> = Vec::new();
+        for key in &link_keys {
+            let Some(value) = doc.field(key) else {
+                continue;
+            };
+            let mut links_here = false;
+            let items: Vec<String> = value
+                .split(',')
+                .map(|item| {
+                    let item = item.trim();
+                    if item.starts_with('[') && parse_adr_ref(item) == Some(id) {
+                        links_here = true;
+                        format!("[{:04}]({})", id, new_filename)
+                    } else {
+                        item.to_string()
                     }
-                }
+                })
+                .collect();
+            if links_here {
+                fields.push((key, items.join(", ")));
             }
         }
-        if changed {
-            let mut out = lines.join("\n");
-            if !out.ends_with('\n') {
-                out.push('\n');
-            }
-            repo.write_string(&a.path, &out)?;
+        if !fields.is_empty() {
+            doc.set_fields(&fields);
+            repo.write_string(&a.path, &doc.render())?;
         }
     }
 
@@ -377,18 +297,255 @@ pub fn reformat<R: AdrRepository>(repo: &R, cfg: &Config, id: u32) -> Result<Adr
         .into_iter()
         .find(|a| a.number == target.number)
         .ok_or_else(|| anyhow!("Reformatted ADR not found"))?;
-    Ok(updated)
+    Ok((updated, warnings))
+}
+
+/// Splits an ADR into the metadata radr does not manage and the body following the
+/// title heading and metadata block. Anything that has to be dropped is reported
+/// in `warnings`.
+fn split_for_render(raw: &str, title: &str, warnings: &mut Vec<String>) -> (Extras, String) {
+    let doc = AdrDocument::parse(raw);
+    let mut extras = Extras::default();
+    match doc.front_matter_yaml() {
+        Some(Ok(Value::Mapping(map))) => {
+            for (k, v) in map {
+                let managed = k.as_str().is_some_and(|k| MANAGED_FM_KEYS.contains(&k));
+                if !managed {
+                    extras.front_matter.insert(k, v);
+                }
+            }
+        }
+        Some(Ok(Value::Null)) | None => {}
+        Some(_) => warnings.push("front matter is not a YAML mapping and was dropped".to_string()),
+    }
+    for (key, value) in doc.fields() {
+        if !META_KEYS.contains(&key) {
+            extras.header_lines.push(format!("{}: {}", key, value));
+        }
+    }
+    let mut body = String::new();
+    // A heading that is not the title is body content
+    if let Some(h) = doc.heading() {
+        if !doc.has_classic_heading() && h != title {
+            body.push_str(&format!("# {}\n\n", h));
+        }
+    }
+    // The history is re-rendered from the parsed metadata
+    let keeps_history_section = doc.has_metadata_front_matter();
+    for section in doc.sections() {
+        let is_history = section
+            .name()
+            .is_some_and(|n| n.eq_ignore_ascii_case(HISTORY_SECTION));
+        if keeps_history_section || !is_history {
+            body.push_str(&section.render());
+        }
+    }
+    // Dropping the history section leaves the blank lines before it behind
+    body.truncate(body.trim_end().len());
+    if !body.is_empty() {
+        body.push('\n');
+    }
+    (extras, body)
 }
 
-pub fn reformat_all<R: AdrRepository>(repo: &R, cfg: &Config) -> Result<Vec<AdrMeta>> {
+fn yaml_key_display(key: &Value) -> String {
+    match key.as_str() {
+        Some(s) => s.to_string(),
+        None => serde_yaml::to_string(key)
+            .map(|s| s.trim_end().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// Appends `map` as YAML entries, if any.
+fn push_yaml_entries(content: &mut String, map: &Mapping) {
+    if map.is_empty() {
+        return;
+    }
+    if let Ok(yaml) = serde_yaml::to_string(map) {
+        content.push_str(&yaml);
+    }
+}
+
+/// `Supersedes` (or typed link) line value listing `numbers`, each linked to its
+/// file when known.
+fn refs_value(numbers: &[u32], by_number: &HashMap<u32, String>) -> String {
+    numbers
+        .iter()
+        .map(|n| match by_number.get(n) {
+            Some(fname) => format!("[{:04}]({})", n, fname),
+            None => format!("{:04}", n),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Appends the supersede links, deprecation reason, typed links, tags and status
+/// history as front matter fields.
+fn push_front_matter_tail(content: &mut String, meta: &AdrMeta) {
+    if !meta.supersedes.is_empty() {
+        content.push_str(&format!("supersedes: {}\n", yaml_refs(&meta.supersedes)));
+    }
+    if let Some(n) = meta.superseded_by {
+        content.push_str(&format!("superseded_by: {}\n", n));
+    }
+    if let Some(reason) = &meta.deprecation_reason {
+        content.push_str(&format!("deprecation_reason: {}\n", yaml_scalar(reason)));
+    }
+    for kind in LinkKind::ALL {
+        let targets = link_targets(&meta.links, kind);
+        if !targets.is_empty() {
+            content.push_str(&format!("{}: {}\n", kind.yaml_key(), yaml_refs(&targets)));
+        }
+    }
+    if !meta.tags.is_empty() {
+        content.push_str(&format!("tags: {}\n", yaml_list(&meta.tags)));
+    }
+    if !meta.history.is_empty() {
+        content.push_str(&history_yaml(&meta.history));
+    }
+}
+
+/// Appends a `Deciders: Ada, Grace` style line per role with people.
+fn push_people_lines(content: &mut String, people: &People) {
+    for role in Role::ALL {
+        let names = people.get(role);
+        if !names.is_empty() {
+            content.push_str(&format!("{}: {}\n", role.header_key(), names.join(", ")));
+        }
+    }
+}
+
+/// Appends the people as front matter lists. MADR always lists `deciders`,
+/// `consulted` and `informed`, empty or not.
+fn push_people_yaml(content: &mut String, people: &People, madr: bool) {
+    for role in Role::ALL {
+        let names = people.get(role);
+        if !names.is_empty() || (madr && role != Role::Author) {
+            content.push_str(&format!("{}: {}\n", role.yaml_key(), yaml_list(names)));
+        }
+    }
+}
+
+/// Renders an ADR in the layout selected by `cfg` (front matter or `# ADR` header),
+/// followed by its metadata lines and `body`. `by_number` maps ADR numbers to
+/// filenames so `Supersedes` can link to the target file. In the MADR layout and
+/// the full front matter mode all metadata goes to front matter. `extras` are
+/// appended after radr's own fields; unknown header lines open the body when the
+/// layout has no metadata lines. The status history goes to a `history` front
+/// matter list, or to a `## Status History` section closing the body.
+pub(crate) fn render_adr(
+    cfg: &Config,
+    meta: &AdrMeta,
+    by_number: &HashMap<u32, String>,
+    body: &str,
+    extras: &Extras,
+) -> String {
+    let mut content = String::new();
+    let mut header_block = String::new();
+    for l in &extras.header_lines {
+        header_block.push_str(l);
+        header_block.push('\n');
+    }
+    if !header_block.is_empty() {
+        header_block.push('\n');
+    }
+    if cfg.is_madr() {
+        let rest = &extras.front_matter;
+        content.push_str("---\n");
+        content.push_str(&format!("status: {}\n", escape_yaml(&meta.status)));
+        content.push_str(&format!("date: {}\n", meta.date));
+        push_people_yaml(&mut content, &meta.people, true);
+        push_front_matter_tail(&mut content, meta);
+        push_yaml_entries(&mut content, rest);
+        content.push_str("---\n\n");
+        content.push_str(&format!("# {}\n\n", meta.title));
+        content.push_str(&header_block);
+        content.push_str(body);
+        return content;
+    }
+    if cfg.full_front_matter() {
+        content.push_str("---\n");
+        content.push_str(&format!("title: {}\n", escape_yaml(&meta.title)));
+        content.push_str(&format!("number: {}\n", meta.number));
+        content.push_str(&format!("date: {}\n", meta.date));
+        content.push_str(&format!("status: {}\n", escape_yaml(&meta.status)));
+        push_people_yaml(&mut content, &meta.people, false);
+        push_front_matter_tail(&mut content, meta);
+        push_yaml_entries(&mut content, &extras.front_matter);
+        content.push_str("---\n\n");
+        content.push_str(&header_block);
+        content.push_str(body);
+        return content;
+    }
+    if cfg.front_matter {
+        content.push_str("---\n");
+        content.push_str(&format!("title: {}\n", escape_yaml(&meta.title)));
+        if !meta.tags.is_empty() {
+            content.push_str(&format!("tags: {}\n", yaml_list(&meta.tags)));
+        }
+        push_yaml_entries(&mut content, &extras.front_matter);
+        content.push_str("---\n\n");
+    } else {
+        content.push_str(&format!("# ADR {:04}: {}\n\n", meta.number, meta.title));
+    }
+    content.push_str(&format!("Date: {}\n", meta.date));
+    content.push_str(&format!("Status: {}\n", meta.status));
+    if let Some(n) = meta.superseded_by {
+        content.push_str(&format!("Superseded-by: {:04}\n", n));
+    }
+    if !meta.supersedes.is_empty() {
+        content.push_str(&format!(
+            "Supersedes: {}\n",
+            refs_value(&meta.supersedes, by_number)
+        ));
+    }
+    if let Some(reason) = &meta.deprecation_reason {
+        content.push_str(&format!("Deprecation-reason: {}\n", reason));
+    }
+    for kind in LinkKind::ALL {
+        let targets = link_targets(&meta.links, kind);
+        if !targets.is_empty() {
+            content.push_str(&format!(
+                "{}: {}\n",
+                kind.header_key(),
+                refs_value(&targets, by_number)
+            ));
+        }
+    }
+    if !meta.tags.is_empty() && !cfg.front_matter {
+        content.push_str(&format!("Tags: {}\n", meta.tags.join(", ")));
+    }
+    push_people_lines(&mut content, &meta.people);
+    for l in &extras.header_lines {
+        content.push_str(l);
+        content.push('\n');
+    }
+    content.push('\n');
+    content.push_str(body);
+    if !meta.history.is_empty() {
+        if !body.is_empty() {
+            content.push('\n');
+        }
+        content.push_str(&history_section(&meta.history));
+    }
+    content
+}
+
+pub fn reformat_all<R: AdrRepository>(
+    repo: &R,
+    cfg: &Config,
+) -> Result<(Vec<AdrMeta>, Vec<String>)> {
     let adrs = repo.list()?;
     let ids: Vec<u32> = adrs.iter().map(|a| a.number).collect();
     let mut out = Vec::with_capacity(ids.len());
+    let mut warnings = Vec::new();
     for id in ids {
-        let m = reformat(repo, cfg, id)?;
+        let (m, w) = reformat(repo, cfg, id)?;
         out.push(m);
+        warnings.extend(w);
     }
-    Ok(out)
+    Ok((out, warnings))
 }
 
 pub fn list_and_index<R: AdrRepository>(repo: &R, cfg: &Config) -> Result<Vec<AdrMeta>> {
@@ -397,207 +554,551 @@ pub fn list_and_index<R: AdrRepository>(repo: &R, cfg: &Config) -> Result<Vec<Ad
     Ok(adrs)
 }
 
+/// Moves an ADR to `status`, stamps `Date` with today and records the change,
+/// optionally with who made it, in the status history. `people` are added to the
+/// ADR's authors, deciders, consulted and informed lists. The status must be listed
+/// in the configured lifecycle, and the change must be an allowed transition
+/// unless `force` is set.
+pub fn set_status<R: AdrRepository>(
+    repo: &R,
+    cfg: &Config,
+    id_or_title: &str,
+    status: &str,
+    force: bool,
+    by: Option<&str>,
+    people: &People,
+) -> Result<AdrMeta> {
+    change_status(repo, cfg, id_or_title, status, force, by, |target| {
+        people_fields(&target.people, people)
+    })
+}
+
+/// Retires an ADR without replacement: moves it to `Deprecated` like `set_status`
+/// and records `reason` as its deprecation reason.
+pub fn deprecate<R: AdrRepository>(
+    repo: &R,
+    cfg: &Config,
+    id_or_title: &str,
+    reason: &str,
+    force: bool,
+    by: Option<&str>,
+    people: &People,
+) -> Result<AdrMeta> {
+    let reason = reason.trim();
+    if reason.is_empty() {
+        return Err(anyhow!("A deprecation reason is required"));
+    }
+    change_status(repo, cfg, id_or_title, "Deprecated", force, by, |target| {
+        let mut fields = vec![("Deprecation-reason", reason.to_string())];
+        fields.extend(people_fields(&target.people, people));
+        fields
+    })
+}
+
+/// Metadata fields for the roles `added` brings new names to, merged with `current`.
+fn people_fields(current: &People, added: &People) -> Vec<(&'static str, String)> {
+    let merged = current.merged(added);
+    Role::ALL
+        .into_iter()
+        .filter(|r| merged.get(*r) != current.get(*r))
+        .map(|r| (r.header_key(), merged.get(r).join(", ")))
+        .collect()
+}
+
+/// Shared by `set_status` and `deprecate`: checks the move against the lifecycle,
+/// then writes `Date`, `Status` and the `fields` computed for the target ADR and
+/// records the change.
+fn change_status<R: AdrRepository>(
+    repo: &R,
+    cfg: &Config,
+    id_or_title: &str,
+    status: &str,
+    force: bool,
+    by: Option<&str>,
+    fields: impl FnOnce(&AdrMeta) -> Vec<(&'static str, String)>,
+) -> Result<AdrMeta> {
+    let target = find_adr(&repo.list()?, id_or_title)?;
+    let lifecycle = &cfg.lifecycle;
+    if status.trim().to_ascii_lowercase().starts_with("superseded") {
+        return Err(anyhow!(
+            "Use `radr supersede {:04} <title>` to supersede an ADR",
+            target.number
+        ));
+    }
+    let status = lifecycle
+        .resolve(status)
+        .ok_or_else(|| {
+            anyhow!(
+                "Unknown status '{}'. Allowed statuses: {}",
+                status.trim(),
+                lifecycle.statuses.join(", ")
+            )
+        })?
+        .to_string();
+    if !force && !lifecycle.allows(&target.status, &status) {
+        let allowed = lifecycle.targets(&target.status).unwrap_or_default();
+        return Err(anyhow!(
+            "ADR {:04} cannot move from '{}' to '{}' (allowed: {}). Use --force to override",
+            target.number,
+            target.status,
+            status,
+            if allowed.is_empty() {
+                "none".to_string()
+            } else {
+                allowed.join(", ")
+            }
+        ));
+    }
+    let today = Local::now().format("%Y-%m-%d").to_string();
+    let mut fields = fields(&target);
+    fields.insert(0, ("Status", status.clone()));
+    fields.insert(0, ("Date", today.clone()));
+    edit_adr(repo, cfg, &target, |doc| {
+        doc.set_fields(&fields);
+        record_status(doc, &target, &status, &today, by);
+    })
+}
+
 pub fn accept<R: AdrRepository>(repo: &R, cfg: &Config, id_or_title: &str) -> Result<AdrMeta> {
+    set_status(
+        repo,
+        cfg,
+        id_or_title,
+        "Accepted",
+        false,
+        None,
+        &People::default(),
+    )
+}
+
+pub fn reject<R: AdrRepository>(repo: &R, cfg: &Config, id_or_title: &str) -> Result<AdrMeta> {
+    set_status(
+        repo,
+        cfg,
+        id_or_title,
+        "Rejected",
+        false,
+        None,
+        &People::default(),
+    )
+}
+
+/// Adds `tags` to an ADR, skipping ones it already has (ignoring case).
+pub fn add_tags<R: AdrRepository>(
+    repo: &R,
+    cfg: &Config,
+    id_or_title: &str,
+    tags: &[String],
+) -> Result<AdrMeta> {
+    let target = find_adr(&repo.list()?, id_or_title)?;
+    let mut updated = target.tags.clone();
+    updated.extend(tags.iter().cloned());
+    write_tags(repo, cfg, &target, parse_list(&updated.join(",")))
+}
+
+/// Removes `tags` (ignoring case) from an ADR. Fails when it has none of them.
+pub fn remove_tags<R: AdrRepository>(
+    repo: &R,
+    cfg: &Config,
+    id_or_title: &str,
+    tags: &[String],
+) -> Result<AdrMeta> {
+    let target = find_adr(&repo.list()?, id_or_title)?;
+    if !tags.iter().any(|t| list_contains(&target.tags, t)) {
+        return Err(anyhow!(
+            "ADR {:04} is not tagged {}",
+            target.number,
+            tags.join(", ")
+        ));
+    }
+    let updated = target
+        .tags
+        .iter()
+        .filter(|t| !list_contains(tags, t))
+        .cloned()
+        .collect();
+    write_tags(repo, cfg, &target, updated)
+}
+
+fn write_tags<R: AdrRepository>(
+    repo: &R,
+    cfg: &Config,
+    target: &AdrMeta,
+    tags: Vec<String>,
+) -> Result<AdrMeta> {
+    edit_adr(repo, cfg, target, |doc| doc.set_tags(&tags))
+}
+
+/// Records that ADR `from` has a `kind` relation to ADR `to`, adding the reciprocal
+/// entry (e.g. `Amended-by`) to `to`. Linking twice is a no-op. Returns the
+/// updated `from` and `to`.
+pub fn link<R: AdrRepository>(
+    repo: &R,
+    cfg: &Config,
+    from: &str,
+    to: &str,
+    kind: LinkKind,
+) -> Result<(AdrMeta, AdrMeta)> {
     let adrs = repo.list()?;
-    // Try by number, else by title (case-insensitive exact match)
-    let target = match parse_number(id_or_title) {
-        Ok(n) if adrs.iter().any(|a| a.number == n) => adrs
-            .into_iter()
-            .find(|a| a.number == n)
-            .ok_or_else(|| anyhow!("ADR not found by id: {}", n))?,
-        _ => {
-            let lower = id_or_title.trim().to_ascii_lowercase();
-            adrs.into_iter()
-                .find(|a| a.title.to_ascii_lowercase() == lower)
-                .ok_or_else(|| anyhow!("ADR not found by id or title: {}", id_or_title))?
+    let (from, to) = (find_adr(&adrs, from)?, find_adr(&adrs, to)?);
+    if from.number == to.number {
+        return Err(anyhow!("ADR {:04} cannot link to itself", from.number));
+    }
+    write_links(repo, from.number, kind, |t| {
+        if !t.contains(&to.number) {
+            t.push(to.number);
         }
-    };
-
-    let mut content = repo.read_string(&target.path)?;
-    let today = Local::now().format("%Y-%m-%d").to_string();
-    if let Some(stripped) = content.strip_prefix("---\n") {
-        if let Some(end) = stripped.find("\n---\n") {
-            let fm_block = &stripped[..end];
-            let rest = &stripped[end + 5..];
-            let mut lines: Vec<String> = rest.lines().map(|s| s.to_string()).collect();
-            let mut found_status = false;
-            let mut found_date = false;
-            for l in &mut lines {
-                if l.starts_with("Status:") {
-                    *l = "Status: Accepted".to_string();
-                    found_status = true;
-                }
-                if l.starts_with("Date:") {
-                    *l = format!("Date: {}", today);
-                    found_date = true;
-                }
-            }
-            if !found_status {
-                lines.insert(0, "Status: Accepted".to_string());
-            }
-            if !found_date {
-                lines.insert(0, format!("Date: {}", today));
-            }
-            let mut out = String::new();
-            out.push_str("---\n");
-            out.push_str(fm_block);
-            out.push_str("\n---\n");
-            out.push_str(&lines.join("\n"));
-            out.push('\n');
-            content = out;
+    })?;
+    write_links(repo, to.number, kind.reciprocal(), |t| {
+        if !t.contains(&from.number) {
+            t.push(from.number);
         }
+    })?;
+    refresh_links(repo, cfg, from.number, to.number)
+}
+
+/// Removes the `kind` relation (every kind when `None`) from ADR `from` to `to`,
+/// together with its reciprocal entry. Returns the updated `from` and `to`.
+pub fn unlink<R: AdrRepository>(
+    repo: &R,
+    cfg: &Config,
+    from: &str,
+    to: &str,
+    kind: Option<LinkKind>,
+) -> Result<(AdrMeta, AdrMeta)> {
+    let adrs = repo.list()?;
+    let (from, to) = (find_adr(&adrs, from)?, find_adr(&adrs, to)?);
+    let linked: Vec<LinkKind> = from
+        .links
+        .iter()
+        .filter(|l| l.target == to.number && kind.is_none_or(|k| k == l.kind))
+        .map(|l| l.kind)
+        .collect();
+    if linked.is_empty() {
+        return Err(anyhow!(
+            "ADR {:04} has no {} link to {:04}",
+            from.number,
+            kind.map(LinkKind::header_key).unwrap_or("typed"),
+            to.number
+        ));
+    }
+    for k in linked {
+        write_links(repo, from.number, k, |t| t.retain(|n| *n != to.number))?;
+        write_links(repo, to.number, k.reciprocal(), |t| {
+            t.retain(|n| *n != from.number)
+        })?;
+    }
+    refresh_links(repo, cfg, from.number, to.number)
+}
+
+/// Rewrites the `kind` link line (or front matter field) of ADR `number` after
+/// applying `update` to its targets; an empty list removes the field.
+fn write_links<R: AdrRepository>(
+    repo: &R,
+    number: u32,
+    kind: LinkKind,
+    update: impl FnOnce(&mut Vec<u32>),
+) -> Result<()> {
+    let adrs = repo.list()?;
+    let adr = adrs
+        .iter()
+        .find(|a| a.number == number)
+        .ok_or_else(|| anyhow!("ADR {:04} not found", number))?;
+    let mut targets = link_targets(&adr.links, kind);
+    update(&mut targets);
+
+    let mut doc = AdrDocument::parse(&repo.read_string(&adr.path)?);
+    if targets.is_empty() {
+        doc.remove_field(kind.header_key());
     } else {
-        let mut lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
-        let mut found_status = false;
-        let mut found_date = false;
-        for l in &mut lines {
-            if l.starts_with("Status:") {
-                *l = "Status: Accepted".to_string();
-                found_status = true;
-            }
-            if l.starts_with("Date:") {
-                *l = format!("Date: {}", today);
-                found_date = true;
-            }
-        }
-        if !found_status {
-            let insert_at = if !lines.is_empty() { 1 } else { 0 };
-            lines.insert(insert_at, "Status: Accepted".to_string());
-        }
-        if !found_date {
-            lines.insert(1, format!("Date: {}", today));
-        }
-        content = lines.join("\n");
-        if !content.ends_with('\n') {
-            content.push('\n');
-        }
+        doc.set_fields(&[(kind.header_key(), refs_value(&targets, &file_names(&adrs)))]);
     }
-    repo.write_string(&target.path, &content)?;
+    repo.write_string(&adr.path, &doc.render())
+}
 
-    // refresh index and return updated meta
-    let adrs2 = repo.list()?;
-    write_index(repo, cfg, &adrs2)?;
-    let updated = adrs2
-        .into_iter()
-        .find(|a| a.number == target.number)
-        .ok_or_else(|| anyhow!("Updated ADR not found"))?;
-    Ok(updated)
+/// Number -> filename map used to link ADRs to each other.
+fn file_names(adrs: &[AdrMeta]) -> HashMap<u32, String> {
+    let mut by_number = HashMap::new();
+    for a in adrs {
+        if let Some(fname) = a.path.file_name().and_then(OsStr::to_str) {
+            by_number.insert(a.number, fname.to_string());
+        }
+    }
+    by_number
 }
 
-pub fn reject<R: AdrRepository>(repo: &R, cfg: &Config, id_or_title: &str) -> Result<AdrMeta> {
+/// Rewrites the index and returns the current metadata of the two linked ADRs.
+fn refresh_links<R: AdrRepository>(
+    repo: &R,
+    cfg: &Config,
+    from: u32,
+    to: u32,
+) -> Result<(AdrMeta, AdrMeta)> {
     let adrs = repo.list()?;
-    let target = match parse_number(id_or_title) {
+    write_index(repo, cfg, &adrs)?;
+    let get = |n: u32| {
+        adrs.iter()
+            .find(|a| a.number == n)
+            .cloned()
+            .ok_or_else(|| anyhow!("ADR {:04} not found", n))
+    };
+    Ok((get(from)?, get(to)?))
+}
+
+/// Looks up an ADR in the repository by number, else by title, the way the
+/// status commands do.
+pub fn resolve_adr<R: AdrRepository>(repo: &R, id_or_title: &str) -> Result<AdrMeta> {
+    find_adr(&repo.list()?, id_or_title)
+}
+
+/// Resolves an ADR by number, else by title (case-insensitive exact match).
+fn find_adr(adrs: &[AdrMeta], id_or_title: &str) -> Result<AdrMeta> {
+    match parse_number(id_or_title) {
         Ok(n) if adrs.iter().any(|a| a.number == n) => adrs
-            .into_iter()
+            .iter()
             .find(|a| a.number == n)
-            .ok_or_else(|| anyhow!("ADR not found by id: {}", n))?,
+            .cloned()
+            .ok_or_else(|| anyhow!("ADR not found by id: {}", n)),
         _ => {
             let lower = id_or_title.trim().to_ascii_lowercase();
-            adrs.into_iter()
+            adrs.iter()
                 .find(|a| a.title.to_ascii_lowercase() == lower)
-                .ok_or_else(|| anyhow!("ADR not found by id or title: {}", id_or_title))?
+                .cloned()
+                .ok_or_else(|| anyhow!("ADR not found by id or title: {}", id_or_title))
         }
-    };
+    }
+}
 
-    let mut content = repo.read_string(&target.path)?;
-    let today = Local::now().format("%Y-%m-%d").to_string();
-    if let Some(stripped) = content.strip_prefix("---\n") {
-        if let Some(end) = stripped.find("\n---\n") {
-            let fm_block = &stripped[..end];
-            let rest = &stripped[end + 5..];
-            let mut lines: Vec<String> = rest.lines().map(|s| s.to_string()).collect();
-            let mut found_status = false;
-            let mut found_date = false;
-            for l in &mut lines {
-                if l.starts_with("Status:") {
-                    *l = "Status: Rejected".to_string();
-                    found_status = true;
-                }
-                if l.starts_with("Date:") {
-                    *l = format!("Date: {}", today);
-                    found_date = true;
-                }
-            }
-            if !found_status {
-                lines.insert(0, "Status: Rejected".to_string());
-            }
-            if !found_date {
-                lines.insert(0, format!("Date: {}", today));
+/// Appends the move of `current` to `status` to the history of `doc`. An ADR
+/// without history first gets an entry for its current status and date, so the
+/// proposal date survives the `Date` update.
+fn record_status(
+    doc: &mut AdrDocument,
+    current: &AdrMeta,
+    status: &str,
+    date: &str,
+    by: Option<&str>,
+) {
+    if doc.history().is_empty() {
+        doc.push_history(StatusChange {
+            date: current.date.clone(),
+            status: current.status.clone(),
+            by: None,
+        });
+    }
+    doc.push_history(StatusChange {
+        date: date.to_string(),
+        status: status.to_string(),
+        by: by.map(str::to_string),
+    });
+}
+
+/// Applies `edit` to the `AdrDocument` of `target`, then refreshes the index and
+/// returns the re-parsed metadata.
+fn edit_adr<R: AdrRepository>(
+    repo: &R,
+    cfg: &Config,
+    target: &AdrMeta,
+    edit: impl FnOnce(&mut AdrDocument),
+) -> Result<AdrMeta> {
+    let mut doc = AdrDocument::parse(&repo.read_string(&target.path)?);
+    edit(&mut doc);
+    repo.write_string(&target.path, &doc.render())?;
+
+    let adrs = repo.list()?;
+    write_index(repo, cfg, &adrs)?;
+    adrs.into_iter()
+        .find(|a| a.number == target.number)
+        .ok_or_else(|| anyhow!("Updated ADR not found"))
+}
+
+pub(crate) fn render_index(cfg: &Config, adrs: &[AdrMeta]) -> String {
+    let mut content = String::new();
+
+    content.push_str("# Architecture Decision Records\n\n");
+    let by_number = file_names(adrs);
+    if cfg.index_tags == "sections" {
+        let untagged: Vec<&AdrMeta> = adrs.iter().filter(|a| a.tags.is_empty()).collect();
+        let groups = tag_groups(adrs);
+        let untagged = (!untagged.is_empty()).then(|| ("Untagged".to_string(), untagged));
+        for (tag, members) in groups.into_iter().chain(untagged) {
+            content.push_str(&format!("## {}\n\n", tag));
+            for a in members {
+                content.push_str(&index_line(a, &by_number, cfg.index_deciders));
             }
-            let mut out = String::new();
-            out.push_str("---\n");
-            out.push_str(fm_block);
-            out.push_str("\n---\n");
-            out.push_str(&lines.join("\n"));
-            out.push('\n');
-            content = out;
+            content.push('\n');
         }
     } else {
-        let mut lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
-        let mut found_status = false;
-        let mut found_date = false;
-        for l in &mut lines {
-            if l.starts_with("Status:") {
-                *l = "Status: Rejected".to_string();
-                found_status = true;
-            }
-            if l.starts_with("Date:") {
-                *l = format!("Date: {}", today);
-                found_date = true;
+        for a in adrs {
+            content.push_str(&index_line(a, &by_number, cfg.index_deciders));
+        }
+        content.push('\n');
+    }
+    if cfg.index_tags == "files" {
+        let groups = tag_groups(adrs);
+        if !groups.is_empty() {
+            content.push_str("## Tags\n\n");
+            for (tag, members) in &groups {
+                content.push_str(&format!(
+                    "- [{}]({}) ({})\n",
+                    tag,
+                    tag_index_name(cfg, tag),
+                    members.len()
+                ));
             }
+            content.push('\n');
         }
-        if !found_status {
-            let insert_at = if !lines.is_empty() { 1 } else { 0 };
-            lines.insert(insert_at, "Status: Rejected".to_string());
+    }
+    if cfg.index_graph {
+        content.push_str("## Supersede graph\n\n```mermaid\n");
+        content.push_str(&graph::render(GraphFormat::Mermaid, adrs));
+        content.push_str("```\n");
+    }
+    content
+}
+
+/// One index entry: title link, status, dates, typed links, tags and, with
+/// `show_deciders`, the deciders.
+fn index_line(a: &AdrMeta, by_number: &HashMap<u32, String>, show_deciders: bool) -> String {
+    let fname = a.path.file_name().and_then(OsStr::to_str).unwrap_or("");
+    let status_display = if let Some(n) = a.superseded_by {
+        if let Some(target) = by_number.get(&n) {
+            format!("Superseded by [{:04}]({})", n, target)
+        } else {
+            format!("Superseded by {:04}", n)
         }
-        if !found_date {
-            lines.insert(1, format!("Date: {}", today));
+    } else if let (Some(reason), "deprecated") = (&a.deprecation_reason, status_class(&a.status)) {
+        format!("{} ({})", a.status, reason)
+    } else {
+        a.status.clone()
+    };
+    let mut line = format!(
+        "- [{:04}: {}]({}) — Status: {} — Proposed: {}",
+        a.number, a.title, fname, status_display, a.created
+    );
+    if let Some(decided) = &a.decided {
+        line.push_str(&format!(" — Decided: {}", decided));
+    }
+    for kind in LinkKind::ALL {
+        let targets = link_targets(&a.links, kind);
+        if !targets.is_empty() {
+            line.push_str(&format!(
+                " — {}: {}",
+                kind.header_key(),
+                refs_value(&targets, by_number)
+            ));
         }
-        content = lines.join("\n");
-        if !content.ends_with('\n') {
-            content.push('\n');
+    }
+    if !a.tags.is_empty() {
+        line.push_str(&format!(" — Tags: {}", a.tags.join(", ")));
+    }
+    if show_deciders && !a.people.deciders.is_empty() {
+        line.push_str(&format!(" — Deciders: {}", a.people.deciders.join(", ")));
+    }
+    line.push('\n');
+    line
+}
+
+/// ADRs per tag, ordered by tag (case-insensitive). Each group is named after the
+/// first spelling of its tag.
+fn tag_groups(adrs: &[AdrMeta]) -> Vec<(String, Vec<&AdrMeta>)> {
+    let mut groups: Vec<(String, Vec<&AdrMeta>)> = Vec::new();
+    for a in adrs {
+        for tag in &a.tags {
+            match groups.iter_mut().find(|(t, _)| t.eq_ignore_ascii_case(tag)) {
+                Some((_, members)) => members.push(a),
+                None => groups.push((tag.clone(), vec![a])),
+            }
         }
     }
-    repo.write_string(&target.path, &content)?;
+    groups.sort_by_key(|(t, _)| t.to_ascii_lowercase());
+    groups
+}
 
-    let adrs2 = repo.list()?;
-    write_index(repo, cfg, &adrs2)?;
-    let updated = adrs2
+/// First line of every per-tag index, followed by the tag.
+const TAG_INDEX_HEADING: &str = "# Architecture Decision Records: ";
+
+/// File name of the per-tag index next to `cfg.index_name`, e.g. `index-database.md`.
+pub(crate) fn tag_index_name(cfg: &Config, tag: &str) -> String {
+    let index = Path::new(&cfg.index_name);
+    let stem = index.file_stem().and_then(OsStr::to_str).unwrap_or("index");
+    match index.extension().and_then(OsStr::to_str) {
+        Some(ext) => format!("{}-{}.{}", stem, slugify(tag), ext),
+        None => format!("{}-{}", stem, slugify(tag)),
+    }
+}
+
+/// The per-tag indexes `cfg.index_tags = "files"` asks for, as file names and
+/// contents; none for the other settings.
+pub(crate) fn render_tag_indexes(cfg: &Config, adrs: &[AdrMeta]) -> Vec<(String, String)> {
+    if cfg.index_tags != "files" {
+        return Vec::new();
+    }
+    let by_number = file_names(adrs);
+    tag_groups(adrs)
         .into_iter()
-        .find(|a| a.number == target.number)
-        .ok_or_else(|| anyhow!("Updated ADR not found"))?;
-    Ok(updated)
+        .map(|(tag, members)| {
+            let mut content = format!("{}{}\n\n", TAG_INDEX_HEADING, tag);
+            for a in members {
+                content.push_str(&index_line(a, &by_number, cfg.index_deciders));
+            }
+            (tag_index_name(cfg, &tag), content)
+        })
+        .collect()
 }
 
-fn write_index<R: AdrRepository>(repo: &R, cfg: &Config, adrs: &[AdrMeta]) -> Result<()> {
-    let mut content = String::new();
-    content.push_str("# Architecture Decision Records\n\n");
-    // Build map from number -> filename for linking
-    let mut by_number: HashMap<u32, String> = HashMap::new();
-    for a in adrs {
-        if let Some(fname) = a.path.file_name().and_then(OsStr::to_str) {
-            by_number.insert(a.number, fname.to_string());
+pub(crate) fn write_index<R: AdrRepository>(
+    repo: &R,
+    cfg: &Config,
+    adrs: &[AdrMeta],
+) -> Result<()> {
+    let content = render_index(cfg, adrs);
+    let idx = idx_path(&cfg.adr_dir, &cfg.index_name);
+    repo.write_string(&idx, &content)?;
+    let tag_indexes = render_tag_indexes(cfg, adrs);
+    for (name, content) in &tag_indexes {
+        repo.write_string(&idx_path(&cfg.adr_dir, name), content)?;
+    }
+    // Per-tag indexes of tags no ADR carries anymore, or of every tag once
+    // index_tags is no longer "files"
+    for (name, _) in read_tag_indexes(repo, cfg)? {
+        if !tag_indexes.iter().any(|(n, _)| *n == name) {
+            repo.remove_file(&idx_path(&cfg.adr_dir, &name))?;
         }
     }
-    for a in adrs {
-        let fname = a.path.file_name().and_then(OsStr::to_str).unwrap_or("");
-        let status_display = if let Some(n) = a.superseded_by {
-            if let Some(target) = by_number.get(&n) {
-                format!("Superseded by [{:04}]({})", n, target)
-            } else {
-                format!("Superseded by {:04}", n)
-            }
-        } else {
-            a.status.clone()
+    Ok(())
+}
+
+/// The per-tag indexes written by [`write_index`] that are currently in the ADR
+/// directory, as file names and contents. A file counts when it is named like
+/// [`tag_index_name`] and starts with the per-tag heading, so other files that
+/// merely share the prefix are kept.
+pub(crate) fn read_tag_indexes<R: AdrRepository>(
+    repo: &R,
+    cfg: &Config,
+) -> Result<Vec<(String, String)>> {
+    let index = Path::new(&cfg.index_name);
+    let stem = index.file_stem().and_then(OsStr::to_str).unwrap_or("index");
+    let mut found = Vec::new();
+    for path in repo.list_files()? {
+        let Some(name) = path.file_name().and_then(OsStr::to_str) else {
+            continue;
         };
-        content.push_str(&format!(
-            "- [{:04}: {}]({}) — Status: {} — Date: {}\n",
-            a.number, a.title, fname, status_display, a.date
-        ));
+        let named_like = path
+            .file_stem()
+            .and_then(OsStr::to_str)
+            .is_some_and(|s| s.starts_with(&format!("{}-", stem)))
+            && path.extension() == index.extension();
+        if !named_like {
+            continue;
+        }
+        if let Ok(content) = repo.read_string(&path) {
+            if content.starts_with(TAG_INDEX_HEADING) {
+                found.push((name.to_string(), content));
+            }
+        }
     }
-    content.push('\n');
-    let idx = idx_path(&cfg.adr_dir, &cfg.index_name);
-    repo.write_string(&idx, &content)
+    Ok(found)
 }
 
 #[cfg(test)]
@@ -618,7 +1119,8 @@ mod tests {
             ..Config::default()
         };
 
-        let meta = create_new_adr(&repo, &cfg, "First Decision", None).unwrap();
+        let meta =
+            create_new_adr(&repo, &cfg, "First Decision", &[], &[], &People::default()).unwrap();
         assert_eq!(meta.number, 1);
         assert!(meta.path.exists());
         assert_eq!(meta.status, "Proposed");
@@ -642,8 +1144,16 @@ mod tests {
             ..Config::default()
         };
 
-        let old = create_new_adr(&repo, &cfg, "Choose X", None).unwrap();
-        let new_meta = create_new_adr(&repo, &cfg, "Choose Y", Some(old.number)).unwrap();
+        let old = create_new_adr(&repo, &cfg, "Choose X", &[], &[], &People::default()).unwrap();
+        let new_meta = create_new_adr(
+            &repo,
+            &cfg,
+            "Choose Y",
+            &[old.number],
+            &[],
+            &People::default(),
+        )
+        .unwrap();
         mark_superseded(&repo, &cfg, old.number, new_meta.number).unwrap();
 
         let old_path = cfg.adr_dir.join(format!(
@@ -672,14 +1182,52 @@ mod tests {
             ..Config::default()
         };
 
-        let old = create_new_adr(&repo, &cfg, "Choose X", None).unwrap();
-        let new_meta = create_new_adr(&repo, &cfg, "Choose Y", Some(old.number)).unwrap();
+        let old = create_new_adr(&repo, &cfg, "Choose X", &[], &[], &People::default()).unwrap();
+        let new_meta = create_new_adr(
+            &repo,
+            &cfg,
+            "Choose Y",
+            &[old.number],
+            &[],
+            &People::default(),
+        )
+        .unwrap();
         mark_superseded(&repo, &cfg, old.number, new_meta.number).unwrap();
 
         let index = cfg.adr_dir.join("index.md");
         let idx = repo.read_string(&index).unwrap();
         // Ensure the old ADR's status contains a link to the new ADR file
         assert!(idx.contains("Status: Superseded by [0002](0002-choose-y.md)"));
+        assert!(!idx.contains("```mermaid"));
+    }
+
+    #[test]
+    fn test_index_embeds_mermaid_graph_when_enabled() {
+        let dir = tempdir().unwrap();
+        let adr_dir = dir.path().join("adrs");
+        let repo = FsAdrRepository::new(&adr_dir);
+        let cfg = Config {
+            adr_dir: adr_dir.clone(),
+            index_graph: true,
+            ..Config::default()
+        };
+
+        let old = create_new_adr(&repo, &cfg, "Choose X", &[], &[], &People::default()).unwrap();
+        let new_meta = create_new_adr(
+            &repo,
+            &cfg,
+            "Choose Y",
+            &[old.number],
+            &[],
+            &People::default(),
+        )
+        .unwrap();
+        mark_superseded(&repo, &cfg, old.number, new_meta.number).unwrap();
+
+        let idx = repo.read_string(&cfg.adr_dir.join("index.md")).unwrap();
+        assert!(idx.contains("## Supersede graph\n\n```mermaid\nflowchart TD\n"));
+        assert!(idx.contains("adr0002 -->|supersedes| adr0001"));
+        assert!(idx.ends_with("```\n"));
     }
 
     #[test]
@@ -696,7 +1244,15 @@ mod tests {
         cfg.format = "mdx".into();
         cfg.front_matter = true;
 
-        let meta = create_new_adr(&repo, &cfg, "Front Matter Title", None).unwrap();
+        let meta = create_new_adr(
+            &repo,
+            &cfg,
+            "Front Matter Title",
+            &[],
+            &[],
+            &People::default(),
+        )
+        .unwrap();
         assert!(meta.path.ends_with("0001-front-matter-title.mdx"));
         let c = repo.read_string(&meta.path).unwrap();
         assert!(c.starts_with("---\n"));
@@ -717,7 +1273,7 @@ mod tests {
             ..Config::default()
         };
 
-        let m1 = create_new_adr(&repo, &cfg, "Adopt Z", None).unwrap();
+        let m1 = create_new_adr(&repo, &cfg, "Adopt Z", &[], &[], &People::default()).unwrap();
         let today = chrono::Local::now().format("%Y-%m-%d").to_string();
 
         let updated1 = accept(&repo, &cfg, &format!("{}", m1.number)).unwrap();
@@ -726,7 +1282,7 @@ mod tests {
         assert!(c1.contains("Status: Accepted"));
         assert!(c1.contains(&format!("Date: {}", today)));
 
-        let _m2 = create_new_adr(&repo, &cfg, "Pick W", None).unwrap();
+        let _m2 = create_new_adr(&repo, &cfg, "Pick W", &[], &[], &People::default()).unwrap();
         let updated2 = accept(&repo, &cfg, "Pick W").unwrap();
         assert_eq!(updated2.status, "Accepted");
     }
@@ -775,7 +1331,7 @@ mod tests {
             template: Some(dir.path().join("missing.tpl")),
             ..Config::default()
         };
-        let err = create_new_adr(&repo, &cfg, "X", None).unwrap_err();
+        let err = create_new_adr(&repo, &cfg, "X", &[], &[], &People::default()).unwrap_err();
         let msg = format!("{}", err);
         assert!(msg.contains("Reading template"));
     }
@@ -797,7 +1353,8 @@ mod tests {
             ..Config::default()
         };
 
-        let meta = create_new_adr(&repo, &cfg, "Next After Gap", None).unwrap();
+        let meta =
+            create_new_adr(&repo, &cfg, "Next After Gap", &[], &[], &People::default()).unwrap();
         assert_eq!(meta.number, 6);
         assert!(meta.path.ends_with("0006-next-after-gap.md"));
     }
@@ -820,7 +1377,8 @@ mod tests {
             template: Some(tpl_path.clone()),
             ..Config::default()
         };
-        let meta = create_new_adr(&repo, &cfg, "Use Template", Some(3)).unwrap();
+        let meta =
+            create_new_adr(&repo, &cfg, "Use Template", &[3], &[], &People::default()).unwrap();
         let content = repo.read_string(&meta.path).unwrap();
         let today = chrono::Local::now().format("%Y-%m-%d").to_string();
         assert!(content.contains("# ADR 0001: Use Template"));
@@ -846,7 +1404,7 @@ mod tests {
         };
 
         // Create new ADR to get number 2
-        let new_meta = create_new_adr(&repo, &cfg, "New", None).unwrap();
+        let new_meta = create_new_adr(&repo, &cfg, "New", &[], &[], &People::default()).unwrap();
         mark_superseded(&repo, &cfg, 1, new_meta.number).unwrap();
         let updated = repo.read_string(&old_path).unwrap();
         assert!(updated.contains("Status: Superseded by 0002"));
@@ -865,7 +1423,7 @@ mod tests {
             ..Config::default()
         };
 
-        let m1 = create_new_adr(&repo, &cfg, "Choose DB", None).unwrap();
+        let m1 = create_new_adr(&repo, &cfg, "Choose DB", &[], &[], &People::default()).unwrap();
         let today = chrono::Local::now().format("%Y-%m-%d").to_string();
 
         let _ = accept(&repo, &cfg, "0001").unwrap();
@@ -873,7 +1431,7 @@ mod tests {
         assert!(c1.contains("Status: Accepted"));
         assert!(c1.contains(&format!("Date: {}", today)));
 
-        let _m2 = create_new_adr(&repo, &cfg, "Use Queue", None).unwrap();
+        let _m2 = create_new_adr(&repo, &cfg, "Use Queue", &[], &[], &People::default()).unwrap();
         let updated2 = accept(&repo, &cfg, "use queue").unwrap();
         assert_eq!(updated2.status, "Accepted");
     }
@@ -890,7 +1448,7 @@ mod tests {
             ..Config::default()
         };
 
-        let m1 = create_new_adr(&repo, &cfg, "Reject Me", None).unwrap();
+        let m1 = create_new_adr(&repo, &cfg, "Reject Me", &[], &[], &People::default()).unwrap();
         let today = chrono::Local::now().format("%Y-%m-%d").to_string();
 
         let updated1 = reject(&repo, &cfg, &format!("{}", m1.number)).unwrap();
@@ -899,8 +1457,423 @@ mod tests {
         assert!(c1.contains("Status: Rejected"));
         assert!(c1.contains(&format!("Date: {}", today)));
 
-        let _m2 = create_new_adr(&repo, &cfg, "Another One", None).unwrap();
+        let _m2 = create_new_adr(&repo, &cfg, "Another One", &[], &[], &People::default()).unwrap();
         let updated2 = reject(&repo, &cfg, "another one").unwrap();
         assert_eq!(updated2.status, "Rejected");
     }
+
+    #[test]
+    fn test_madr_create_accept_and_supersede_update_front_matter() {
+        let dir = tempdir().unwrap();
+        let adr_dir = dir.path().join("adrs");
+        let repo = FsAdrRepository::new(&adr_dir);
+        let cfg = Config {
+            adr_dir: adr_dir.clone(),
+            layout: "madr".into(),
+            ..Config::default()
+        };
+
+        let old =
+            create_new_adr(&repo, &cfg, "Use Postgres", &[], &[], &People::default()).unwrap();
+        let c = repo.read_string(&old.path).unwrap();
+        assert!(c.starts_with("---\nstatus: Proposed\ndate: "));
+        assert!(c.contains("deciders: []\nconsulted: []\ninformed: []\n---\n\n# Use Postgres\n"));
+        assert!(c.contains("## Decision Drivers"));
+        assert!(c.contains("## Pros and Cons of the Options"));
+        assert!(!c.contains("Status:"));
+
+        let accepted = accept(&repo, &cfg, "1").unwrap();
+        assert_eq!(accepted.status, "Accepted");
+        assert_eq!(accepted.title, "Use Postgres");
+        let c = repo.read_string(&old.path).unwrap();
+        assert!(c.starts_with("---\nstatus: Accepted\n"));
+
+        let new_meta = create_new_adr(
+            &repo,
+            &cfg,
+            "Use SQLite",
+            &[old.number],
+            &[],
+            &People::default(),
+        )
+        .unwrap();
+        mark_superseded(&repo, &cfg, old.number, new_meta.number).unwrap();
+        let c = repo.read_string(&old.path).unwrap();
+        assert!(c.contains("status: Superseded by 0002\n"));
+        assert!(c.contains("superseded_by: 2\n---\n"));
+        assert!(!c.contains("Status:"));
+        let adrs = repo.list().unwrap();
+        assert_eq!(adrs[0].superseded_by, Some(2));
+        assert_eq!(adrs[1].supersedes, vec![1]);
+    }
+
+    #[test]
+    fn test_reformat_between_classic_and_madr() {
+        let dir = tempdir().unwrap();
+        let adr_dir = dir.path().join("adrs");
+        std::fs::create_dir_all(&adr_dir).unwrap();
+        std::fs::write(
+            adr_dir.join("0001-pick-x.md"),
+            "---\nstatus: Accepted\ndate: 2024-01-01\ndeciders:\n  - alice\n---\n\n# Pick X\n\n## Context and Problem Statement\n\nWhy.\n",
+        )
+        .unwrap();
+        let repo = FsAdrRepository::new(&adr_dir);
+        let madr = Config {
+            adr_dir: adr_dir.clone(),
+            layout: "madr".into(),
+            ..Config::default()
+        };
+
+        reformat(&repo, &madr, 1).unwrap();
+        let c = repo.read_string(&adr_dir.join("0001-pick-x.md")).unwrap();
+        assert_eq!(
+            c,
+            "---\nstatus: Accepted\ndate: 2024-01-01\ndeciders: [alice]\nconsulted: []\ninformed: []\n---\n\n# Pick X\n\n## Context and Problem Statement\n\nWhy.\n"
+        );
+
+        let classic = Config {
+            adr_dir: adr_dir.clone(),
+            ..Config::default()
+        };
+        let (m, _) = reformat(&repo, &classic, 1).unwrap();
+        assert_eq!(m.status, "Accepted");
+        let c = repo.read_string(&m.path).unwrap();
+        assert_eq!(
+            c,
+            "# ADR 0001: Pick X\n\nDate: 2024-01-01\nStatus: Accepted\nDeciders: alice\n\n## Context and Problem Statement\n\nWhy.\n"
+        );
+    }
+
+    #[test]
+    fn test_reformat_with_history_is_idempotent() {
+        let dir = tempdir().unwrap();
+        let adr_dir = dir.path().join("adrs");
+        std::fs::create_dir_all(&adr_dir).unwrap();
+        let path = adr_dir.join("0001-pick-x.md");
+        std::fs::write(
+            &path,
+            "# ADR 0001: Pick X\n\nDate: 2024-01-01\nStatus: Accepted\n\n## Context\n\nWhy.\n\n## Status History\n\n- 2024-01-01: Proposed\n- 2024-02-01: Accepted\n",
+        )
+        .unwrap();
+        let repo = FsAdrRepository::new(&adr_dir);
+        for cfg in [
+            Config {
+                adr_dir: adr_dir.clone(),
+                ..Config::default()
+            },
+            Config {
+                adr_dir: adr_dir.clone(),
+                layout: "madr".into(),
+                ..Config::default()
+            },
+        ] {
+            reformat(&repo, &cfg, 1).unwrap();
+            let once = repo.read_string(&path).unwrap();
+            reformat(&repo, &cfg, 1).unwrap();
+            assert_eq!(repo.read_string(&path).unwrap(), once);
+            assert!(
+                once.ends_with("Why.\n") || once.ends_with("Accepted\n"),
+                "{}",
+                once
+            );
+            assert!(!once.contains("\n\n\n"), "{}", once);
+        }
+    }
+
+    #[test]
+    fn test_full_front_matter_mode_writes_and_updates_yaml() {
+        let dir = tempdir().unwrap();
+        let adr_dir = dir.path().join("adrs");
+        let repo = FsAdrRepository::new(&adr_dir);
+        let cfg = Config {
+            adr_dir: adr_dir.clone(),
+            format: "mdx".into(),
+            front_matter: true,
+            front_matter_mode: "full".into(),
+            ..Config::default()
+        };
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+
+        let old = create_new_adr(&repo, &cfg, "Pick: A", &[], &[], &People::default()).unwrap();
+        let c = repo.read_string(&old.path).unwrap();
+        assert_eq!(
+            c,
+            format!(
+                "---\ntitle: \"Pick: A\"\nnumber: 1\ndate: {}\nstatus: Proposed\n---\n\n{}",
+                today, SKELETON
+            )
+        );
+
+        let new_meta =
+            create_new_adr(&repo, &cfg, "Pick B", &[1], &[], &People::default()).unwrap();
+        mark_superseded(&repo, &cfg, old.number, new_meta.number).unwrap();
+        reject(&repo, &cfg, "2").unwrap();
+        let c = repo.read_string(&old.path).unwrap();
+        assert!(c.contains(&format!(
+            "status: Superseded by 0002\nsuperseded_by: 2\nhistory:\n- date: {0}\n  status: Proposed\n- date: {0}\n  status: Superseded by 0002\n---\n",
+            today
+        )));
+        let c = repo.read_string(&new_meta.path).unwrap();
+        assert!(c.contains("status: Rejected\nsupersedes: 1\nhistory:\n"));
+        assert!(c.contains("\n---\n\n## Context\n"));
+        assert!(!c.contains("Status:"));
+
+        let adrs = repo.list().unwrap();
+        assert_eq!(adrs[0].title, "Pick: A");
+        assert_eq!(adrs[0].superseded_by, Some(2));
+        assert_eq!(adrs[1].status, "Rejected");
+        assert_eq!(adrs[1].supersedes, vec![1]);
+    }
+
+    #[test]
+    fn test_reformat_moves_body_metadata_into_full_front_matter() {
+        let dir = tempdir().unwrap();
+        let adr_dir = dir.path().join("adrs");
+        std::fs::create_dir_all(&adr_dir).unwrap();
+        std::fs::write(
+            adr_dir.join("0002-pick-y.md"),
+            "# ADR 0002: Pick Y\n\nDate: 2024-05-06\nStatus: Accepted\nSupersedes: 0001\n\n## Context\n\nBody\n",
+        )
+        .unwrap();
+        let repo = FsAdrRepository::new(&adr_dir);
+        let cfg = Config {
+            adr_dir: adr_dir.clone(),
+            front_matter: true,
+            front_matter_mode: "full".into(),
+            ..Config::default()
+        };
+        let (m, _) = reformat(&repo, &cfg, 2).unwrap();
+        assert_eq!(m.supersedes, vec![1]);
+        assert_eq!(
+            repo.read_string(&m.path).unwrap(),
+            "---\ntitle: Pick Y\nnumber: 2\ndate: 2024-05-06\nstatus: Accepted\nsupersedes: 1\n---\n\n## Context\n\nBody\n"
+        );
+    }
+
+    #[test]
+    fn test_reformat_carries_unknown_keys_and_header_lines() {
+        let dir = tempdir().unwrap();
+        let adr_dir = dir.path().join("adrs");
+        std::fs::create_dir_all(&adr_dir).unwrap();
+        std::fs::write(
+            adr_dir.join("0001-pick-x.mdx"),
+            "---\ntitle: Pick X\ntags: [db, infra]\nsidebar_position: 3\nslug: /pick-x\n---\n\nDate: 2024-01-01\nDeciders: Alice, Bob\nStatus: Accepted\nTicket: OPS-12\n\n## Context\n\nWhy.\n",
+        )
+        .unwrap();
+        let repo = FsAdrRepository::new(&adr_dir);
+        let mut cfg = Config {
+            adr_dir: adr_dir.clone(),
+            format: "mdx".into(),
+            front_matter: true,
+            ..Config::default()
+        };
+
+        let (m, warnings) = reformat(&repo, &cfg, 1).unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(
+            repo.read_string(&m.path).unwrap(),
+            "---\ntitle: Pick X\ntags: [db, infra]\nsidebar_position: 3\nslug: /pick-x\n---\n\nDate: 2024-01-01\nStatus: Accepted\nDeciders: Alice, Bob\nTicket: OPS-12\n\n## Context\n\nWhy.\n"
+        );
+
+        cfg.front_matter_mode = "full".into();
+        let (m, _) = reformat(&repo, &cfg, 1).unwrap();
+        let c = repo.read_string(&m.path).unwrap();
+        assert!(c.contains("status: Accepted\ndeciders: [Alice, Bob]\ntags: [db, infra]\nsidebar_position: 3\nslug: /pick-x\n---\n\nTicket: OPS-12\n\n## Context\n"));
+
+        // Back to title-only: header lines return to the metadata block
+        cfg.front_matter_mode = "title".into();
+        let (m, _) = reformat(&repo, &cfg, 1).unwrap();
+        assert!(repo
+            .read_string(&m.path)
+            .unwrap()
+            .contains("Status: Accepted\nDeciders: Alice, Bob\nTicket: OPS-12\n\n## Context"));
+
+        // The classic layout has nowhere to put front matter keys
+        let classic = Config {
+            adr_dir: adr_dir.clone(),
+            ..Config::default()
+        };
+        let (m, warnings) = reformat(&repo, &classic, 1).unwrap();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].starts_with("0001: front matter key 'sidebar_position' dropped"));
+        // Tags are managed metadata and move to a header line
+        let c = repo.read_string(&m.path).unwrap();
+        assert!(c.starts_with(
+            "# ADR 0001: Pick X\n\nDate: 2024-01-01\nStatus: Accepted\nTags: db, infra\nDeciders: Alice, Bob\n"
+        ));
+    }
+
+    #[test]
+    fn test_split_for_render_warns_on_non_mapping_front_matter() {
+        let mut warnings = Vec::new();
+        let (extras, body) = split_for_render("---\n- a\n- b\n---\n\nBody\n", "T", &mut warnings);
+        assert!(extras.front_matter.is_empty());
+        assert_eq!(body, "Body\n");
+        assert_eq!(
+            warnings,
+            vec!["front matter is not a YAML mapping and was dropped".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_status_updates_leave_body_examples_alone() {
+        let dir = tempdir().unwrap();
+        let adr_dir = dir.path().join("adrs");
+        std::fs::create_dir_all(&adr_dir).unwrap();
+        let body = "## Decision\n\nWrite headers like this:\n\n```\nDate: 2020-01-01\nStatus: Proposed\nSuperseded-by: 0009\n```\n\n> Status: Proposed\n\n| Field | Example |\n|---|---|\n| Status: | Proposed |\n";
+        let path = adr_dir.join("0001-headers.md");
+        std::fs::write(
+            &path,
+            format!(
+                "# ADR 0001: Headers\n\nDate: 2024-01-01\nStatus: Proposed\n\n{}",
+                body
+            ),
+        )
+        .unwrap();
+        let repo = FsAdrRepository::new(&adr_dir);
+        let cfg = Config {
+            adr_dir: adr_dir.clone(),
+            ..Config::default()
+        };
+
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let m = accept(&repo, &cfg, "1").unwrap();
+        assert_eq!(m.status, "Accepted");
+        let c = repo.read_string(&path).unwrap();
+        assert!(c.starts_with("# ADR 0001: Headers\n\nDate: "));
+        assert!(c.ends_with(&format!(
+            "Status: Accepted\n\n{}\n## Status History\n\n- 2024-01-01: Proposed\n- {}: Accepted\n",
+            body, today
+        )));
+
+        let new_meta = create_new_adr(&repo, &cfg, "Next", &[1], &[], &People::default()).unwrap();
+        mark_superseded(&repo, &cfg, 1, new_meta.number).unwrap();
+        let c = repo.read_string(&path).unwrap();
+        assert!(c.contains("Status: Superseded by 0002\nSuperseded-by: 0002\n\n## Decision"));
+        assert!(c.contains(body));
+        assert!(c.ends_with(&format!("- {}: Superseded by 0002\n", today)));
+        assert_eq!(repo.list().unwrap()[0].superseded_by, Some(2));
+    }
+
+    #[test]
+    fn test_set_status_follows_lifecycle() {
+        let dir = tempdir().unwrap();
+        let adr_dir = dir.path().join("adrs");
+        let repo = FsAdrRepository::new(&adr_dir);
+        let mut cfg = Config {
+            adr_dir: adr_dir.clone(),
+            ..Config::default()
+        };
+        cfg.lifecycle.statuses.push("On Hold".into());
+        cfg.lifecycle
+            .transitions
+            .insert("Rejected".into(), vec!["Proposed".into()]);
+
+        create_new_adr(&repo, &cfg, "Pick X", &[], &[], &People::default()).unwrap();
+        let m = set_status(&repo, &cfg, "1", "on hold", false, None, &People::default()).unwrap();
+        assert_eq!(m.status, "On Hold");
+        assert!(repo
+            .read_string(&m.path)
+            .unwrap()
+            .contains("Status: On Hold\n"));
+
+        let err =
+            set_status(&repo, &cfg, "1", "Paused", false, None, &People::default()).unwrap_err();
+        assert!(err.to_string().contains("Unknown status 'Paused'. Allowed statuses: Proposed, Accepted, Rejected, Deprecated, On Hold"));
+
+        reject(&repo, &cfg, "Pick X").unwrap();
+        let err = accept(&repo, &cfg, "1").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "ADR 0001 cannot move from 'Rejected' to 'Accepted' (allowed: Proposed). Use --force to override"
+        );
+        let m = set_status(&repo, &cfg, "1", "Accepted", true, None, &People::default()).unwrap();
+        assert_eq!(m.status, "Accepted");
+
+        let err = set_status(
+            &repo,
+            &cfg,
+            "1",
+            "Superseded",
+            false,
+            None,
+            &People::default(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("radr supersede 0001"));
+    }
+
+    #[test]
+    fn test_index_groups_by_tag() {
+        let dir = tempdir().unwrap();
+        let adr_dir = dir.path().join("adrs");
+        let repo = FsAdrRepository::new(&adr_dir);
+        let mut cfg = Config {
+            adr_dir: adr_dir.clone(),
+            index_tags: "sections".into(),
+            ..Config::default()
+        };
+        let tags = |t: &[&str]| t.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        create_new_adr(
+            &repo,
+            &cfg,
+            "Use Postgres",
+            &[],
+            &tags(&["database"]),
+            &People::default(),
+        )
+        .unwrap();
+        create_new_adr(
+            &repo,
+            &cfg,
+            "Encrypt Backups",
+            &[],
+            &tags(&["Security", "database"]),
+            &People::default(),
+        )
+        .unwrap();
+        create_new_adr(&repo, &cfg, "Use Rust", &[], &[], &People::default()).unwrap();
+
+        let idx = repo.read_string(&adr_dir.join("index.md")).unwrap();
+        let database = idx.find("## database\n\n- [0001: Use Postgres]").unwrap();
+        let security = idx
+            .find("## Security\n\n- [0002: Encrypt Backups]")
+            .unwrap();
+        let untagged = idx.find("## Untagged\n\n- [0003: Use Rust]").unwrap();
+        assert!(database < security && security < untagged);
+        assert!(idx.contains("— Tags: Security, database\n"));
+
+        cfg.index_tags = "files".into();
+        write_index(&repo, &cfg, &repo.list().unwrap()).unwrap();
+        let idx = repo.read_string(&adr_dir.join("index.md")).unwrap();
+        assert!(idx.contains(
+            "## Tags\n\n- [database](index-database.md) (2)\n- [Security](index-security.md) (1)\n"
+        ));
+        let per_tag = repo
+            .read_string(&adr_dir.join("index-database.md"))
+            .unwrap();
+        assert!(per_tag
+            .starts_with("# Architecture Decision Records: database\n\n- [0001: Use Postgres]"));
+        assert!(!per_tag.contains("Use Rust"));
+
+        let m = remove_tags(&repo, &cfg, "2", &tags(&["DATABASE"])).unwrap();
+        assert_eq!(m.tags, vec!["Security"]);
+        let m = add_tags(&repo, &cfg, "Use Rust", &tags(&["lang", "Lang"])).unwrap();
+        assert_eq!(m.tags, vec!["lang"]);
+        assert!(remove_tags(&repo, &cfg, "3", &tags(&["nope"])).is_err());
+
+        // Indexes of tags that are gone are removed; unrelated files stay
+        repo.write_string(&adr_dir.join("index-notes.md"), "# Notes\n")
+            .unwrap();
+        remove_tags(&repo, &cfg, "2", &tags(&["Security"])).unwrap();
+        assert!(!adr_dir.join("index-security.md").exists());
+        assert!(adr_dir.join("index-database.md").exists());
+        assert!(adr_dir.join("index-lang.md").exists());
+        cfg.index_tags = "none".into();
+        write_index(&repo, &cfg, &repo.list().unwrap()).unwrap();
+        assert!(!adr_dir.join("index-database.md").exists());
+        assert!(!adr_dir.join("index-lang.md").exists());
+        assert!(adr_dir.join("index-notes.md").exists());
+        assert!(adr_dir.join("index.md").exists());
+    }
 }
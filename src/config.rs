@@ -1,15 +1,159 @@
-use std::{env, ffi::OsStr, fs, path::PathBuf};
+use std::{
+    collections::BTreeMap,
+    env,
+    ffi::OsStr,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
 
 use anyhow::{anyhow, Context, Result};
-use serde::Deserialize;
+use clap::ValueEnum;
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Deserialize, Serialize, Serializer};
+use serde_json::{json, Value};
+
+use crate::repository::ADR_FILE_PATTERN;
+
+/// Names of the user config file, looked up in `$XDG_CONFIG_HOME/radr` (by
+/// default `~/.config/radr`), in order of preference.
+pub const USER_CONFIG_FILES: &[&str] = &["config.toml", "config.yaml", "config.yml", "config.json"];
+
+/// Config files looked up in the current directory when neither `--config` nor
+/// `RADR_CONFIG` is given, in order of preference.
+pub const CONFIG_FILES: &[&str] = &[
+    "radr.toml",
+    "radr.yaml",
+    "radr.yml",
+    "radr.json",
+    ".radrrc.toml",
+    ".radrrc.yaml",
+    ".radrrc.yml",
+    ".radrrc.json",
+];
+
+/// Config file languages, picked by file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ConfigFormat {
+    #[default]
+    Toml,
+    Yaml,
+    Json,
+}
+
+impl ConfigFormat {
+    /// The format of a config file, from its extension.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let ext = path.extension().and_then(OsStr::to_str).unwrap_or("");
+        match ext.to_ascii_lowercase().as_str() {
+            "toml" => Ok(Self::Toml),
+            "yaml" | "yml" => Ok(Self::Yaml),
+            "json" => Ok(Self::Json),
+            other => Err(anyhow!("Unsupported config extension: {}", other)),
+        }
+    }
+
+    /// Default file name for a config in this format, e.g. `radr.toml`.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Self::Toml => "radr.toml",
+            Self::Yaml => "radr.yaml",
+            Self::Json => "radr.json",
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct Config {
     pub adr_dir: PathBuf,
     pub index_name: String,
     pub template: Option<PathBuf>,
-    pub format: String,     // "md" or "mdx"
-    pub front_matter: bool, // include YAML front matter
+    pub format: String,            // "md" or "mdx"
+    pub front_matter: bool,        // include YAML front matter
+    pub index_graph: bool,         // embed a Mermaid supersede graph in the index
+    pub index_tags: String,        // "none", "sections" or "files": group the index by tag
+    pub index_deciders: bool,      // show each ADR's deciders in the index
+    pub layout: String,            // "classic" or "madr"
+    pub front_matter_mode: String, // "title" or "full": which metadata goes to front matter
+    pub author: Option<String>,    // author of new ADRs; defaults to git user.name
+    pub lifecycle: Lifecycle,
+}
+
+/// Statuses an ADR may take and the status changes `radr status` allows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lifecycle {
+    pub statuses: Vec<String>,
+    /// Allowed target statuses per status; a status without entry may move anywhere
+    pub transitions: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for Lifecycle {
+    fn default() -> Self {
+        Self {
+            statuses: ["Proposed", "Accepted", "Rejected", "Deprecated"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            transitions: BTreeMap::new(),
+        }
+    }
+}
+
+impl Lifecycle {
+    /// The configured spelling of `status`, matched case-insensitively.
+    pub fn resolve(&self, status: &str) -> Option<&str> {
+        let status = status.trim();
+        self.statuses
+            .iter()
+            .find(|s| s.eq_ignore_ascii_case(status))
+            .map(String::as_str)
+    }
+
+    /// Target statuses configured for `from`, if it is restricted. `Superseded by
+    /// NNNN` is looked up as `Superseded`.
+    pub fn targets(&self, from: &str) -> Option<&[String]> {
+        let from = from.trim();
+        let from = if from.to_ascii_lowercase().starts_with("superseded") {
+            "Superseded"
+        } else {
+            from
+        };
+        self.transitions
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(from))
+            .map(|(_, v)| v.as_slice())
+    }
+
+    /// True when an ADR may move from `from` to `to`. Keeping the same status is
+    /// always allowed.
+    pub fn allows(&self, from: &str, to: &str) -> bool {
+        if from.trim().eq_ignore_ascii_case(to.trim()) {
+            return true;
+        }
+        match self.targets(from) {
+            Some(targets) => targets.iter().any(|t| t.eq_ignore_ascii_case(to.trim())),
+            None => true,
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.statuses.is_empty() {
+            return Err(anyhow!("lifecycle.statuses must not be empty"));
+        }
+        let known = |s: &str| self.resolve(s).is_some() || s.eq_ignore_ascii_case("Superseded");
+        for (from, targets) in &self.transitions {
+            for s in std::iter::once(from).chain(targets) {
+                if !known(s) {
+                    return Err(anyhow!(
+                        "lifecycle.transitions mentions '{}', which is not in lifecycle.statuses ({})",
+                        s,
+                        self.statuses.join(", ")
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Default for Config {
@@ -20,75 +164,864 @@ impl Default for Config {
             template: None,
             format: "md".to_string(),
             front_matter: false,
+            index_graph: false,
+            index_tags: "none".to_string(),
+            index_deciders: false,
+            layout: "classic".to_string(),
+            front_matter_mode: "title".to_string(),
+            author: None,
+            lifecycle: Lifecycle::default(),
         }
     }
 }
 
-#[derive(Deserialize, Debug)]
+impl Config {
+    /// True when new and reformatted ADRs use the MADR layout.
+    pub fn is_madr(&self) -> bool {
+        self.layout == "madr"
+    }
+
+    /// True when front matter holds every metadata field, not just the title.
+    pub fn full_front_matter(&self) -> bool {
+        self.front_matter && self.front_matter_mode == "full"
+    }
+
+    /// Every setting by config key, in the order of the config file, as printed
+    /// by `radr config show`.
+    pub fn values(&self) -> Vec<(&'static str, Value)> {
+        vec![
+            ("adr_dir", json!(self.adr_dir)),
+            ("index_name", json!(self.index_name)),
+            ("template", json!(self.template)),
+            ("format", json!(self.format)),
+            ("front_matter", json!(self.front_matter)),
+            ("front_matter_mode", json!(self.front_matter_mode)),
+            ("layout", json!(self.layout)),
+            ("index_graph", json!(self.index_graph)),
+            ("index_tags", json!(self.index_tags)),
+            ("index_deciders", json!(self.index_deciders)),
+            ("author", json!(self.author)),
+            (
+                "lifecycle",
+                json!({
+                    "statuses": self.lifecycle.statuses,
+                    "transitions": self.lifecycle.transitions,
+                }),
+            ),
+        ]
+    }
+}
+
+/// Top-level config keys and, for string options, the values they accept.
+const KEYS: &[(&str, &[&str])] = &[
+    ("adr_dir", &[]),
+    ("index_name", &[]),
+    ("template", &[]),
+    ("format", &["md", "mdx"]),
+    ("front_matter", &[]),
+    ("front_matter_mode", &["title", "full"]),
+    ("layout", &["classic", "madr"]),
+    ("index_graph", &[]),
+    ("index_tags", &["none", "sections", "files"]),
+    ("index_deciders", &[]),
+    ("author", &[]),
+    ("lifecycle", &[]),
+];
+
+/// Keys taking `true` or `false`; environment variables and `--set` also accept
+/// yes/no, on/off and 1/0 for them.
+const BOOL_KEYS: &[&str] = &["front_matter", "index_graph", "index_deciders"];
+
+const LIFECYCLE_KEYS: &[&str] = &["statuses", "transitions"];
+
+/// A config file as written. The doc comments are the descriptions in the JSON
+/// Schema printed by `radr config schema`.
+#[derive(Deserialize, JsonSchema, Debug)]
+#[schemars(
+    title = "radr config",
+    description = "Configuration of radr, the Architecture Decision Record CLI",
+    deny_unknown_fields
+)]
 struct FileConfig {
+    /// JSON Schema of this file, for editors; radr ignores it
+    #[serde(rename = "$schema")]
+    #[allow(dead_code)]
+    schema: Option<String>,
+    /// Directory where ADRs live, relative to the config file. Default: docs/adr
     adr_dir: Option<PathBuf>,
+    /// Name of the index file written to the ADR directory. Default: index.md
     index_name: Option<String>,
+    /// Template for new ADRs, relative to the config file
     template: Option<PathBuf>,
+    /// File extension of new ADRs. Default: md
+    #[schemars(extend("enum" = ["md", "mdx"]))]
     format: Option<String>,
+    /// Add a YAML front matter block to ADRs. Default: false
     front_matter: Option<bool>,
+    /// Embed a Mermaid supersede graph in the index. Default: false
+    index_graph: Option<bool>,
+    /// Group the index by tag. Default: none
+    #[schemars(extend("enum" = ["none", "sections", "files"]))]
+    index_tags: Option<String>,
+    /// Show the deciders of each ADR in the index. Default: false
+    index_deciders: Option<bool>,
+    /// Layout of new and reformatted ADRs. Default: classic
+    #[schemars(extend("enum" = ["classic", "madr"]))]
+    layout: Option<String>,
+    /// Metadata kept in front matter when front_matter is on. Default: title
+    #[schemars(extend("enum" = ["title", "full"]))]
+    front_matter_mode: Option<String>,
+    /// Author of new ADRs when `radr new` or `radr supersede` gets no --author. Default: git config user.name
+    author: Option<String>,
+    /// Statuses ADRs may take and the status changes `radr status` allows
+    lifecycle: Option<FileLifecycle>,
 }
 
-pub fn load_config(cli_path: Option<&PathBuf>) -> Result<Config> {
-    let mut cfg = Config::default();
-
-    let path = if let Some(p) = cli_path {
-        Some(p.clone())
-    } else if let Ok(env_p) = env::var("RADR_CONFIG") {
-        Some(PathBuf::from(env_p))
-    } else {
-        let candidates = [
-            "radr.toml",
-            "radr.yaml",
-            "radr.yml",
-            "radr.json",
-            ".radrrc.toml",
-            ".radrrc.yaml",
-            ".radrrc.yml",
-            ".radrrc.json",
-        ];
-        candidates.iter().map(PathBuf::from).find(|p| p.exists())
-    };
+#[derive(Deserialize, JsonSchema, Debug)]
+#[schemars(deny_unknown_fields)]
+struct FileLifecycle {
+    /// Allowed statuses. Default: Proposed, Accepted, Rejected, Deprecated
+    statuses: Option<Vec<String>>,
+    /// Allowed target statuses per status; statuses without entry may move anywhere
+    transitions: Option<BTreeMap<String, Vec<String>>>,
+}
+
+/// JSON Schema of the config file, for editor completion and validation.
+pub fn config_schema() -> Value {
+    serde_json::to_value(schemars::schema_for!(FileConfig)).expect("schema serializes")
+}
+
+/// The config file to use: the `--config` path, else `RADR_CONFIG`, else the
+/// first of [`CONFIG_FILES`] found in the current directory or one of its parents.
+/// The search stops at the repository root (a directory holding `.git`) or the
+/// filesystem root. `None` means the defaults apply.
+pub fn find_config(cli_path: Option<&PathBuf>) -> Result<Option<PathBuf>> {
+    if let Some(p) = cli_path {
+        return Ok(Some(p.clone()));
+    }
+    if let Ok(env_p) = env::var("RADR_CONFIG") {
+        return Ok(Some(PathBuf::from(env_p)));
+    }
+    let cwd = env::current_dir().context("Reading the current directory")?;
+    Ok(discover_config(&cwd))
+}
+
+/// Looks for a config file in `cwd` and its parents, like git looks for `.git`.
+/// The path is returned relative to `cwd` (e.g. `../../radr.toml`), so paths in
+/// messages stay short.
+fn discover_config(cwd: &Path) -> Option<PathBuf> {
+    let mut prefix = PathBuf::new();
+    for dir in cwd.ancestors() {
+        if let Some(name) = CONFIG_FILES.iter().find(|f| dir.join(f).is_file()) {
+            return Some(prefix.join(name));
+        }
+        if dir.join(".git").exists() {
+            break;
+        }
+        prefix.push("..");
+    }
+    None
+}
+
+/// The user's own config file: the first of [`USER_CONFIG_FILES`] in
+/// `$XDG_CONFIG_HOME/radr`, or in `~/.config/radr` when that variable is unset.
+/// It holds personal defaults, such as `author` or a `template` from a template
+/// library, which every project config overrides.
+pub fn user_config() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))?;
+    let dir = base.join("radr");
+    USER_CONFIG_FILES
+        .iter()
+        .map(|f| dir.join(f))
+        .find(|p| p.is_file())
+}
+
+/// Where an effective setting comes from. Layers apply in the order listed here,
+/// each overriding the ones before.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Origin {
+    Default,
+    /// The user config file, see [`user_config`]
+    User(PathBuf),
+    /// The project config file, see [`find_config`]
+    Project(PathBuf),
+    /// A `RADR_<KEY>` environment variable
+    Env(String),
+    /// A `--set KEY=VALUE` argument
+    Cli(String),
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::User(p) | Self::Project(p) => write!(f, "{}", p.display()),
+            Self::Env(var) => write!(f, "{}", var),
+            Self::Cli(arg) => write!(f, "--set {}", arg),
+        }
+    }
+}
+
+impl Serialize for Origin {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// The effective config with the layer behind each setting and the problems
+/// found in the layers.
+#[derive(Debug, Clone)]
+pub struct LayeredConfig {
+    pub config: Config,
+    /// The layer that set each config key
+    pub origins: BTreeMap<&'static str, Origin>,
+    pub problems: Vec<(Origin, String)>,
+    project_dir: Option<PathBuf>,
+}
 
-    if let Some(p) = path {
-        let ext = p.extension().and_then(OsStr::to_str).unwrap_or("");
-        let contents =
-            fs::read_to_string(&p).with_context(|| format!("Reading config at {}", p.display()))?;
-        let fc: FileConfig = match ext.to_ascii_lowercase().as_str() {
-            "json" => serde_json::from_str(&contents)
-                .with_context(|| format!("Parsing JSON config at {}", p.display()))?,
-            "yaml" | "yml" => serde_yaml::from_str(&contents)
-                .with_context(|| format!("Parsing YAML config at {}", p.display()))?,
-            "toml" => toml::from_str(&contents)
-                .with_context(|| format!("Parsing TOML config at {}", p.display()))?,
-            other => return Err(anyhow!("Unsupported config extension: {}", other)),
+impl LayeredConfig {
+    fn new() -> Self {
+        Self {
+            config: Config::default(),
+            origins: KEYS.iter().map(|(k, _)| (*k, Origin::Default)).collect(),
+            problems: Vec::new(),
+            project_dir: None,
+        }
+    }
+
+    /// Fails with every problem found, grouped by the layer it comes from.
+    pub fn check(&self) -> Result<()> {
+        if self.problems.is_empty() {
+            return Ok(());
+        }
+        let mut groups: Vec<(&Origin, Vec<&str>)> = Vec::new();
+        for (origin, problem) in &self.problems {
+            match groups.iter_mut().find(|(o, _)| *o == origin) {
+                Some((_, problems)) => problems.push(problem),
+                None => groups.push((origin, vec![problem])),
+            }
+        }
+        let blocks: Vec<String> = groups
+            .iter()
+            .map(|(origin, problems)| {
+                format!(
+                    "Invalid config at {}:\n  - {}",
+                    origin,
+                    problems.join("\n  - ")
+                )
+            })
+            .collect();
+        Err(anyhow!(blocks.join("\n")))
+    }
+
+    /// The effective value of `key`, e.g. `format` or `lifecycle.statuses`, and
+    /// the layer that set it.
+    pub fn get(&self, key: &str) -> Result<(Value, Origin)> {
+        let (top, sub) = match key.split_once('.') {
+            Some((top, sub)) => (top, Some(sub)),
+            None => (key, None),
+        };
+        let unknown = || {
+            let mut known: Vec<String> = KEYS.iter().map(|(k, _)| k.to_string()).collect();
+            known.extend(LIFECYCLE_KEYS.iter().map(|k| format!("lifecycle.{}", k)));
+            anyhow!(unknown_key(
+                key,
+                &known.iter().map(String::as_str).collect::<Vec<_>>()
+            ))
+        };
+        let (name, value) = self
+            .config
+            .values()
+            .into_iter()
+            .find(|(k, _)| *k == top)
+            .ok_or_else(unknown)?;
+        let value = match sub {
+            Some(sub) => value.get(sub).cloned().ok_or_else(unknown)?,
+            None => value,
+        };
+        Ok((value, self.origins[name].clone()))
+    }
+
+    fn apply_file(&mut self, path: &Path, origin: Origin) -> Result<()> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Reading config at {}", path.display()))?;
+        let raw: Value = parse_config(path, &contents)?;
+        let fc: FileConfig = parse_config(path, &contents)?;
+        self.apply(&raw, fc, path.parent().unwrap_or(Path::new("")), origin);
+        Ok(())
+    }
+
+    /// Applies one `key=value` setting from the environment or the command line.
+    fn apply_override(&mut self, key: &str, value: &str, origin: Origin) {
+        let value = match parse_setting(key, value) {
+            Ok(v) => v,
+            Err(problem) => {
+                self.problems.push((origin, problem));
+                return;
+            }
+        };
+        let raw = Value::Object([(key.to_string(), value)].into_iter().collect());
+        let fc: FileConfig =
+            serde_json::from_value(raw.clone()).expect("string and boolean settings deserialize");
+        self.apply(&raw, fc, Path::new(""), origin);
+    }
+
+    /// Applies the settings of one layer. Relative paths are resolved against
+    /// `base`, except an `adr_dir` from the user config, which like the default
+    /// belongs to the project. Settings with unsupported values are left alone.
+    fn apply(&mut self, raw: &Value, fc: FileConfig, base: &Path, origin: Origin) {
+        self.problems
+            .extend(check_keys(raw).into_iter().map(|p| (origin.clone(), p)));
+        let Self {
+            config: cfg,
+            origins,
+            ..
+        } = self;
+        let mut set = |key: &'static str, applied: bool| {
+            if applied {
+                origins.insert(key, origin.clone());
+            }
         };
 
         if let Some(d) = fc.adr_dir {
-            cfg.adr_dir = d;
+            cfg.adr_dir = match origin {
+                Origin::User(_) => d,
+                _ => base.join(d),
+            };
+            set("adr_dir", true);
         }
         if let Some(i) = fc.index_name {
             cfg.index_name = i;
+            set("index_name", true);
         }
         if let Some(t) = fc.template {
-            cfg.template = Some(t);
-        }
-        if let Some(f) = fc.format {
-            let f = f.to_ascii_lowercase();
-            if f == "md" || f == "mdx" {
-                cfg.format = f;
-            }
+            cfg.template = Some(base.join(t));
+            set("template", true);
         }
+        set("format", choose("format", fc.format, &mut cfg.format));
+        set(
+            "index_tags",
+            choose("index_tags", fc.index_tags, &mut cfg.index_tags),
+        );
+        set("layout", choose("layout", fc.layout, &mut cfg.layout));
+        set(
+            "front_matter_mode",
+            choose(
+                "front_matter_mode",
+                fc.front_matter_mode,
+                &mut cfg.front_matter_mode,
+            ),
+        );
         if let Some(fm) = fc.front_matter {
             cfg.front_matter = fm;
+            set("front_matter", true);
+        }
+        if let Some(g) = fc.index_graph {
+            cfg.index_graph = g;
+            set("index_graph", true);
+        }
+        if let Some(d) = fc.index_deciders {
+            cfg.index_deciders = d;
+            set("index_deciders", true);
+        }
+        if let Some(a) = fc.author {
+            cfg.author = Some(a);
+            set("author", true);
+        }
+        if let Some(lc) = fc.lifecycle {
+            if let Some(statuses) = lc.statuses {
+                cfg.lifecycle.statuses = statuses;
+                set("lifecycle", true);
+            }
+            if let Some(transitions) = lc.transitions {
+                cfg.lifecycle.transitions = transitions;
+                set("lifecycle", true);
+            }
         }
     }
 
-    Ok(cfg)
+    /// Resolves the ADR directory against the project and checks the merged
+    /// settings.
+    fn finish(&mut self) {
+        if matches!(self.origins["adr_dir"], Origin::Default | Origin::User(_)) {
+            if let Some(dir) = &self.project_dir {
+                self.config.adr_dir = dir.join(&self.config.adr_dir);
+            }
+        }
+        let cfg = &self.config;
+        let mut problems = Vec::new();
+        if self.origins["lifecycle"] != Origin::Default {
+            if let Err(e) = cfg.lifecycle.validate() {
+                problems.push(("lifecycle", e.to_string()));
+            }
+        }
+        if let Some(t) = &cfg.template {
+            if !t.is_file() {
+                problems.push((
+                    "template",
+                    format!("template {} does not exist", t.display()),
+                ));
+            }
+        }
+        let adr_file = Regex::new(ADR_FILE_PATTERN).expect("valid ADR filename pattern");
+        if adr_file.is_match(&cfg.index_name) {
+            problems.push((
+                "index_name",
+                format!(
+                    "index_name '{}' looks like an ADR filename (NNNN-title.md), so the index would be listed as an ADR",
+                    cfg.index_name
+                ),
+            ));
+        }
+        for (key, problem) in problems {
+            self.problems.push((self.origins[key].clone(), problem));
+        }
+    }
+}
+
+/// Builds the effective config: the defaults, then the `user` config file, the
+/// `project` config file, the `RADR_<KEY>` variables found by `env` (e.g.
+/// `RADR_ADR_DIR`, `RADR_FORMAT`) and finally `overrides` in `KEY=VALUE` form.
+/// Unreadable files and syntax or type errors in them are errors; everything
+/// else is collected in [`LayeredConfig::problems`].
+pub fn layered_config(
+    user: Option<&Path>,
+    project: Option<&Path>,
+    env: impl Fn(&str) -> Option<String>,
+    overrides: &[String],
+) -> Result<LayeredConfig> {
+    let mut layers = LayeredConfig::new();
+    if let Some(p) = user {
+        layers.apply_file(p, Origin::User(p.to_path_buf()))?;
+    }
+    if let Some(p) = project {
+        layers.project_dir = Some(p.parent().unwrap_or(Path::new("")).to_path_buf());
+        layers.apply_file(p, Origin::Project(p.to_path_buf()))?;
+    }
+    // The lifecycle is structured, so it only comes from files
+    for (key, _) in KEYS.iter().filter(|(k, _)| *k != "lifecycle") {
+        let var = format!("RADR_{}", key.to_ascii_uppercase());
+        if let Some(value) = env(&var) {
+            layers.apply_override(key, &value, Origin::Env(var));
+        }
+    }
+    for arg in overrides {
+        let origin = Origin::Cli(arg.clone());
+        match arg.split_once('=') {
+            Some((key, value)) => layers.apply_override(key.trim(), value, origin),
+            None => layers
+                .problems
+                .push((origin, "expected KEY=VALUE, e.g. format=mdx".into())),
+        }
+    }
+    layers.finish();
+    Ok(layers)
+}
+
+/// Sets `key` to `value` in the config file at `path`. The file keeps its format
+/// and key order; TOML and YAML files also keep their comments, and a setting
+/// that is only commented out, as `radr init` writes them, is uncommented in
+/// place. The file is left alone when the change would add a problem reported
+/// by [`validate_config`]. Returns the value as written.
+pub fn set_config_value(path: &Path, key: &str, value: &str) -> Result<Value> {
+    let mut value = parse_setting(key, value).map_err(|e| anyhow!(e))?;
+    let problems = check_keys(&Value::Object(
+        [(key.to_string(), value.clone())].into_iter().collect(),
+    ));
+    if !problems.is_empty() {
+        return Err(anyhow!(problems.join("; ")));
+    }
+    if let Some(v) = value.as_str().filter(|_| !allowed_values(key).is_empty()) {
+        value = Value::from(v.to_ascii_lowercase());
+    }
+
+    let before = validate_config(path)?;
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Reading config at {}", path.display()))?;
+    let edited = match ConfigFormat::from_path(path)? {
+        ConfigFormat::Toml => set_toml(&contents, key, &value)
+            .with_context(|| format!("Parsing TOML config at {}", path.display()))?,
+        ConfigFormat::Yaml => set_yaml(&contents, key, &value)?,
+        ConfigFormat::Json => {
+            let mut raw: Value = parse_config(path, &contents)?;
+            let map = raw
+                .as_object_mut()
+                .ok_or_else(|| anyhow!("{} does not hold a JSON object", path.display()))?;
+            map.insert(key.to_string(), value.clone());
+            format!("{}\n", serde_json::to_string_pretty(&raw)?)
+        }
+    };
+    fs::write(path, &edited).with_context(|| format!("Writing config at {}", path.display()))?;
+
+    let added: Vec<String> = match validate_config(path) {
+        Ok(after) => after.into_iter().filter(|p| !before.contains(p)).collect(),
+        Err(e) => vec![format!("{:#}", e)],
+    };
+    if !added.is_empty() {
+        fs::write(path, &contents)
+            .with_context(|| format!("Restoring config at {}", path.display()))?;
+        return Err(anyhow!(
+            "Setting {} would make {} invalid; it was left unchanged:\n  - {}",
+            key,
+            path.display(),
+            added.join("\n  - ")
+        ));
+    }
+    Ok(value)
+}
+
+fn set_toml(contents: &str, key: &str, value: &Value) -> Result<String> {
+    let mut doc: toml_edit::DocumentMut = contents.parse()?;
+    let new = match value {
+        Value::Bool(b) => toml_edit::Value::from(*b),
+        v => toml_edit::Value::from(v.as_str().unwrap_or_default()),
+    };
+    if let Some(current) = doc.get_mut(key).and_then(toml_edit::Item::as_value_mut) {
+        // Keep the comment after the old value
+        let decor = current.decor().clone();
+        *current = new;
+        *current.decor_mut() = decor;
+        return Ok(doc.to_string());
+    }
+    // Settings before the first table belong to the top level
+    let top_level = contents
+        .lines()
+        .take_while(|l| !l.trim_start().starts_with('['))
+        .count();
+    let line = format!("{} = {}", key, new.to_string().trim());
+    if let Some(edited) = uncomment(contents, key, '=', top_level, &line) {
+        return Ok(edited);
+    }
+    doc.insert(key, toml_edit::Item::Value(new));
+    Ok(doc.to_string())
+}
+
+fn set_yaml(contents: &str, key: &str, value: &Value) -> Result<String> {
+    let rendered = serde_yaml::to_string(value)?.trim_end().to_string();
+    let current = Regex::new(&format!(
+        r"(?m)^({}[ \t]*:[ \t]*)([^#\n]*?)([ \t]+#.*)?$",
+        regex::escape(key)
+    ))
+    .expect("valid YAML key pattern");
+    if let Some(caps) = current.captures(contents) {
+        let whole = caps.get(0).expect("whole match");
+        let line = format!(
+            "{}{}{}",
+            &caps[1],
+            rendered,
+            caps.get(3).map_or("", |c| c.as_str())
+        );
+        return Ok(format!(
+            "{}{}{}",
+            &contents[..whole.start()],
+            line,
+            &contents[whole.end()..]
+        ));
+    }
+    let line = format!("{}: {}", key, rendered);
+    if let Some(edited) = uncomment(contents, key, ':', usize::MAX, &line) {
+        return Ok(edited);
+    }
+    let mut edited = contents.to_string();
+    if !edited.is_empty() && !edited.ends_with('\n') {
+        edited.push('\n');
+    }
+    edited.push_str(&line);
+    edited.push('\n');
+    Ok(edited)
+}
+
+/// Replaces the first line among the first `within` lines that comments out
+/// `key` (e.g. `# format = "md"`) with `line`.
+fn uncomment(contents: &str, key: &str, sep: char, within: usize, line: &str) -> Option<String> {
+    let commented = Regex::new(&format!(r"^#\s*{}\s*{}", regex::escape(key), sep))
+        .expect("valid commented key pattern");
+    let lines: Vec<&str> = contents.split_inclusive('\n').collect();
+    let at = lines
+        .iter()
+        .take(within)
+        .position(|l| commented.is_match(l))?;
+    let newline = if lines[at].ends_with('\n') { "\n" } else { "" };
+    let mut edited: String = lines[..at].concat();
+    edited.push_str(line);
+    edited.push_str(newline);
+    edited.push_str(&lines[at + 1..].concat());
+    Some(edited)
+}
+
+/// Builds the effective config for a command from the [`user_config`], the
+/// config picked by [`find_config`], the environment and `overrides` (the
+/// `--set` arguments). See [`layered_config`].
+pub fn load_layers(cli_path: Option<&PathBuf>, overrides: &[String]) -> Result<LayeredConfig> {
+    let user = user_config();
+    let project = find_config(cli_path)?;
+    layered_config(
+        user.as_deref(),
+        project.as_deref(),
+        |var| env::var(var).ok().filter(|v| !v.is_empty()),
+        overrides,
+    )
+}
+
+/// Loads the effective config without command-line overrides. Any problem
+/// reported by [`validate_config`], in any layer, is an error.
+pub fn load_config(cli_path: Option<&PathBuf>) -> Result<Config> {
+    let layers = load_layers(cli_path, &[])?;
+    layers.check()?;
+    Ok(layers.config)
+}
+
+/// Checks the config file at `path` on its own, without using it. Returns one
+/// message per problem: unknown keys (with a suggestion when a known key is
+/// close), unsupported values, a missing template, an `index_name` that would be
+/// read as an ADR, and an inconsistent lifecycle. Unreadable files and syntax or
+/// type errors are returned as errors.
+pub fn validate_config(path: &Path) -> Result<Vec<String>> {
+    let layers = layered_config(None, Some(path), |_| None, &[])?;
+    Ok(layers.problems.into_iter().map(|(_, p)| p).collect())
+}
+
+fn parse_config<T: DeserializeOwned>(path: &Path, contents: &str) -> Result<T> {
+    match ConfigFormat::from_path(path)? {
+        ConfigFormat::Json => serde_json::from_str(contents)
+            .with_context(|| format!("Parsing JSON config at {}", path.display())),
+        ConfigFormat::Yaml => serde_yaml::from_str(contents)
+            .with_context(|| format!("Parsing YAML config at {}", path.display())),
+        ConfigFormat::Toml => toml::from_str(contents)
+            .with_context(|| format!("Parsing TOML config at {}", path.display())),
+    }
+}
+
+/// Sets `target` to `value`, lowercased, when `key` accepts it.
+fn choose(key: &str, value: Option<String>, target: &mut String) -> bool {
+    match value.map(|v| v.to_ascii_lowercase()) {
+        Some(v) if allowed_values(key).contains(&v.as_str()) => {
+            *target = v;
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Reads a setting given as text, in the environment, a `--set` flag or
+/// `radr config set`.
+fn parse_setting(key: &str, value: &str) -> std::result::Result<Value, String> {
+    if key == "lifecycle" || key.starts_with("lifecycle.") {
+        return Err("lifecycle can only be set by editing the config file".into());
+    }
+    if BOOL_KEYS.contains(&key) {
+        return parse_bool(value)
+            .map(Value::Bool)
+            .ok_or_else(|| format!("{} must be true or false, not '{}'", key, value));
+    }
+    Ok(Value::from(value))
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Some(true),
+        "false" | "no" | "off" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+fn allowed_values(key: &str) -> &'static [&'static str] {
+    KEYS.iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, values)| *values)
+        .unwrap_or_default()
+}
+
+/// Reports unknown keys and unsupported values of the string options in a parsed
+/// config file.
+fn check_keys(raw: &Value) -> Vec<String> {
+    let mut problems = Vec::new();
+    let Some(map) = raw.as_object() else {
+        return problems;
+    };
+    let known: Vec<&str> = KEYS.iter().map(|(k, _)| *k).collect();
+    for (key, value) in map {
+        // Editors read `$schema` from JSON configs
+        if key == "$schema" {
+            continue;
+        }
+        if !known.contains(&key.as_str()) {
+            problems.push(unknown_key(key, &known));
+            continue;
+        }
+        let allowed = allowed_values(key);
+        match value.as_str() {
+            Some(v)
+                if !allowed.is_empty() && !allowed.contains(&v.to_ascii_lowercase().as_str()) =>
+            {
+                let mut problem =
+                    format!("{} must be one of {}, not '{}'", key, allowed.join(", "), v);
+                if let Some(s) = did_you_mean(v, allowed) {
+                    problem.push_str(&format!(" (did you mean '{}'?)", s));
+                }
+                problems.push(problem);
+            }
+            _ => {}
+        }
+        if key == "lifecycle" {
+            let known: Vec<String> = LIFECYCLE_KEYS
+                .iter()
+                .map(|k| format!("lifecycle.{}", k))
+                .collect();
+            let known: Vec<&str> = known.iter().map(String::as_str).collect();
+            for sub in value.as_object().into_iter().flat_map(|m| m.keys()) {
+                let sub = format!("lifecycle.{}", sub);
+                if !known.contains(&sub.as_str()) {
+                    problems.push(unknown_key(&sub, &known));
+                }
+            }
+        }
+    }
+    problems
+}
+
+fn unknown_key(key: &str, known: &[&str]) -> String {
+    match did_you_mean(key, known) {
+        Some(s) => format!("unknown key '{}' (did you mean '{}'?)", key, s),
+        None => format!("unknown key '{}'", key),
+    }
+}
+
+/// The candidate closest to `input`, when it is close enough to be a typo.
+fn did_you_mean<'a>(input: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let input = input.to_ascii_lowercase();
+    candidates
+        .iter()
+        .map(|c| (strsim::jaro_winkler(&input, c), *c))
+        .filter(|(score, _)| *score > 0.8)
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, c)| c)
+}
+
+/// Renders `cfg` as a config file. `adr_dir`, `format` and `front_matter` are
+/// written as settings; every other option is listed with its default, commented
+/// out, next to a short description. JSON has no comments, so it only gets the
+/// settings.
+pub fn render_config(cfg: &Config, format: ConfigFormat) -> String {
+    let quote = |s: &str| serde_json::Value::from(s).to_string();
+    let options: [(&str, &str, String, bool); 11] = [
+        (
+            "ADR directory",
+            "adr_dir",
+            quote(&cfg.adr_dir.to_string_lossy()),
+            true,
+        ),
+        (
+            "Index file written to the ADR directory",
+            "index_name",
+            quote(&cfg.index_name),
+            false,
+        ),
+        (
+            "File extension of new ADRs: \"md\" or \"mdx\"",
+            "format",
+            quote(&cfg.format),
+            true,
+        ),
+        (
+            "Add YAML front matter to ADRs",
+            "front_matter",
+            cfg.front_matter.to_string(),
+            true,
+        ),
+        (
+            "Metadata kept in front matter: \"title\" or \"full\"",
+            "front_matter_mode",
+            quote(&cfg.front_matter_mode),
+            false,
+        ),
+        (
+            "Layout of new ADRs: \"classic\" or \"madr\"",
+            "layout",
+            quote(&cfg.layout),
+            false,
+        ),
+        (
+            "Template for new ADRs, with {{NUMBER}}, {{TITLE}}, {{DATE}}, ... placeholders",
+            "template",
+            quote("templates/adr.md"),
+            false,
+        ),
+        (
+            "Embed a Mermaid supersede graph in the index",
+            "index_graph",
+            cfg.index_graph.to_string(),
+            false,
+        ),
+        (
+            "Group the index by tag: \"none\", \"sections\" or \"files\"",
+            "index_tags",
+            quote(&cfg.index_tags),
+            false,
+        ),
+        (
+            "Show the deciders of each ADR in the index",
+            "index_deciders",
+            cfg.index_deciders.to_string(),
+            false,
+        ),
+        (
+            "Author of new ADRs; defaults to git config user.name",
+            "author",
+            quote(cfg.author.as_deref().unwrap_or("Your Name")),
+            false,
+        ),
+    ];
+    let statuses = cfg.lifecycle.statuses.join(", ");
+    match format {
+        ConfigFormat::Json => {
+            let lines: Vec<String> = options
+                .iter()
+                .filter(|o| o.3)
+                .map(|(_, key, value, _)| format!("  \"{}\": {}", key, value))
+                .collect();
+            format!("{{\n{}\n}}\n", lines.join(",\n"))
+        }
+        ConfigFormat::Toml | ConfigFormat::Yaml => {
+            let toml = format == ConfigFormat::Toml;
+            let mut out =
+                String::from("# radr configuration. Commented-out settings show their defaults.\n");
+            for (comment, key, value, set) in &options {
+                let line = if toml {
+                    format!("{} = {}", key, value)
+                } else {
+                    format!("{}: {}", key, value)
+                };
+                out.push_str(&format!(
+                    "\n# {}\n{}{}\n",
+                    comment,
+                    if *set { "" } else { "# " },
+                    line
+                ));
+            }
+            out.push_str("\n# Statuses ADRs may take and the changes `radr status` allows\n");
+            if toml {
+                out.push_str(&format!(
+                    "# [lifecycle]\n# statuses = [{}]\n# [lifecycle.transitions]\n# Proposed = [\"Accepted\", \"Rejected\"]\n",
+                    cfg.lifecycle
+                        .statuses
+                        .iter()
+                        .map(|s| quote(s))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            } else {
+                out.push_str(&format!(
+                    "# lifecycle:\n#   statuses: [{}]\n#   transitions:\n#     Proposed: [Accepted, Rejected]\n",
+                    statuses
+                ));
+            }
+            out
+        }
+    }
 }
 
 #[cfg(test)]
@@ -97,6 +1030,14 @@ mod tests {
     use std::io::Write;
     use tempfile::tempdir;
 
+    /// Loads the project config at `path` like [`load_config`], but without the
+    /// user config and `RADR_*` variables of whoever runs the tests.
+    fn load_project(path: &Path) -> Result<Config> {
+        let layers = layered_config(None, Some(path), |_| None, &[])?;
+        layers.check()?;
+        Ok(layers.config)
+    }
+
     #[test]
     fn test_default_config() {
         let c = Config::default();
@@ -109,9 +1050,10 @@ mod tests {
         let path = dir.path().join("radr.toml");
         let mut f = std::fs::File::create(&path).unwrap();
         writeln!(f, "adr_dir='adrs'\nindex_name='IDX.md'").unwrap();
-        std::env::set_current_dir(dir.path()).unwrap();
-        let cfg = load_config(None).unwrap();
-        assert_eq!(cfg.adr_dir, PathBuf::from("adrs"));
+        let found = discover_config(dir.path()).unwrap();
+        assert_eq!(found, Path::new("radr.toml"));
+        let cfg = load_project(&dir.path().join(found)).unwrap();
+        assert_eq!(cfg.adr_dir, dir.path().join("adrs"));
         assert_eq!(cfg.index_name, "IDX.md");
     }
 
@@ -126,25 +1068,129 @@ mod tests {
         std::fs::write(&yaml, b"adr_dir: env_adrs\nindex_name: ENV.md\n").unwrap();
         // Set env to YAML, but pass CLI JSON path; CLI should win
         std::env::set_var("RADR_CONFIG", &yaml);
-        let cfg = load_config(Some(&json)).unwrap();
-        assert_eq!(cfg.adr_dir, PathBuf::from("cli_adrs"));
+        let path = find_config(Some(&json)).unwrap().unwrap();
+        assert_eq!(path, json);
+        let cfg = load_project(&path).unwrap();
+        // Relative paths are resolved against the config file's directory
+        assert_eq!(cfg.adr_dir, dir.path().join("cli_adrs"));
         assert_eq!(cfg.index_name, "CLI.md");
-        assert_eq!(
-            cfg.template.as_deref(),
-            Some(PathBuf::from("tpl.md").as_path())
-        );
+        assert_eq!(cfg.template, Some(tpl));
         // Defaults remain for new fields unless provided
         assert_eq!(cfg.format, "md");
         assert!(!cfg.front_matter);
+        assert_eq!(cfg.layout, "classic");
         std::env::remove_var("RADR_CONFIG");
     }
 
+    #[test]
+    fn test_layout_madr_and_unknown_rejected() {
+        let dir = tempdir().unwrap();
+        let yaml = dir.path().join("radr.yaml");
+        std::fs::write(&yaml, "layout: MADR\n").unwrap();
+        let cfg = load_project(&yaml).unwrap();
+        assert!(cfg.is_madr());
+        std::fs::write(&yaml, "layout: nygard\n").unwrap();
+        let err = load_project(&yaml).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("layout must be one of classic, madr, not 'nygard'"));
+    }
+
+    #[test]
+    fn test_validation_reports_every_problem() {
+        let dir = tempdir().unwrap();
+        let toml = dir.path().join("radr.toml");
+        std::fs::write(
+            &toml,
+            "adr_directory = 'adrs'\nformat = 'mdxx'\nindex_tags = 'tags'\ntemplate = 'missing.md'\nindex_name = '0001-index.md'\n\n[lifecycle]\nstatus = ['Draft']\n",
+        )
+        .unwrap();
+        let problems = validate_config(&toml).unwrap();
+        assert_eq!(
+            problems[..4],
+            [
+                "unknown key 'adr_directory' (did you mean 'adr_dir'?)",
+                "format must be one of md, mdx, not 'mdxx' (did you mean 'mdx'?)",
+                "index_tags must be one of none, sections, files, not 'tags'",
+                "unknown key 'lifecycle.status' (did you mean 'lifecycle.statuses'?)",
+            ]
+        );
+        assert_eq!(
+            problems[4],
+            format!(
+                "template {} does not exist",
+                dir.path().join("missing.md").display()
+            )
+        );
+        assert!(problems[5].starts_with("index_name '0001-index.md' looks like an ADR filename"));
+        assert_eq!(problems.len(), 6);
+        let err = load_project(&toml).unwrap_err().to_string();
+        assert!(err.starts_with("Invalid config at "));
+        assert!(err.contains("\n  - unknown key 'adr_directory'"));
+
+        // Type errors keep the parser's message
+        std::fs::write(&toml, "front_matter = 'yes'\n").unwrap();
+        assert!(
+            format!("{:#}", validate_config(&toml).unwrap_err()).contains("Parsing TOML config")
+        );
+        std::fs::write(&toml, "format = 'MDX'\n").unwrap();
+        assert!(validate_config(&toml).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_schema_lists_options_and_values() {
+        let schema = config_schema();
+        assert_eq!(schema["title"], "radr config");
+        assert_eq!(schema["additionalProperties"], false);
+        let props = schema["properties"].as_object().unwrap();
+        let keys: Vec<&str> = props.keys().map(String::as_str).collect();
+        let mut expected: Vec<&str> = KEYS.iter().map(|(k, _)| *k).collect();
+        expected.push("$schema");
+        expected.sort_unstable();
+        let mut sorted = keys.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, expected);
+        assert_eq!(props["format"]["enum"], serde_json::json!(["md", "mdx"]));
+        assert_eq!(
+            props["$schema"]["type"],
+            serde_json::json!(["string", "null"])
+        );
+
+        let shipped: Value = serde_json::from_str(include_str!("../radr.schema.json")).unwrap();
+        assert_eq!(
+            shipped, schema,
+            "radr.schema.json is stale; regenerate it with `radr config schema > radr.schema.json`"
+        );
+
+        let dir = tempdir().unwrap();
+        let json = dir.path().join("radr.json");
+        std::fs::write(
+            &json,
+            "{\"$schema\": \"./radr.schema.json\", \"format\": \"mdx\"}",
+        )
+        .unwrap();
+        assert!(validate_config(&json).unwrap().is_empty());
+        assert_eq!(load_project(&json).unwrap().format, "mdx");
+    }
+
+    #[test]
+    fn test_front_matter_mode_requires_front_matter() {
+        let dir = tempdir().unwrap();
+        let toml = dir.path().join("radr.toml");
+        std::fs::write(&toml, "front_matter_mode = \"full\"\n").unwrap();
+        let cfg = load_project(&toml).unwrap();
+        assert_eq!(cfg.front_matter_mode, "full");
+        assert!(!cfg.full_front_matter());
+        std::fs::write(&toml, "front_matter = true\nfront_matter_mode = \"full\"\n").unwrap();
+        assert!(load_project(&toml).unwrap().full_front_matter());
+    }
+
     #[test]
     fn test_unsupported_extension_errors() {
         let dir = tempdir().unwrap();
         let bad = dir.path().join("radr.txt");
         std::fs::write(&bad, "adr_dir=adrs").unwrap();
-        let err = load_config(Some(&bad)).unwrap_err();
+        let err = load_project(&bad).unwrap_err();
         let msg = format!("{}", err);
         assert!(msg.contains("Unsupported config extension"));
     }
@@ -166,8 +1212,9 @@ mod tests {
         // Now set cwd and env; env should win when no CLI provided
         std::env::set_current_dir(dir.path()).unwrap();
         std::env::set_var("RADR_CONFIG", yaml_path.to_str().unwrap());
-        let cfg = load_config(None).unwrap();
-        assert_eq!(cfg.adr_dir, PathBuf::from("env"));
+        let path = find_config(None).unwrap().unwrap();
+        let cfg = load_project(&path).unwrap();
+        assert_eq!(cfg.adr_dir, dir.path().join("env"));
         assert_eq!(cfg.index_name, "ENV.md");
         std::env::remove_var("RADR_CONFIG");
     }
@@ -178,8 +1225,257 @@ mod tests {
         let bad_toml = dir.path().join("radr.toml");
         // invalid toml (missing equals)
         std::fs::write(&bad_toml, "adr_dir 'oops'").unwrap();
-        let err = load_config(Some(&bad_toml)).unwrap_err();
+        let err = load_project(&bad_toml).unwrap_err();
         let msg = format!("{}", err);
         assert!(msg.contains("Parsing TOML config"));
     }
+
+    #[test]
+    fn test_lifecycle_from_toml() {
+        let dir = tempdir().unwrap();
+        let toml = dir.path().join("radr.toml");
+        std::fs::write(
+            &toml,
+            "[lifecycle]\nstatuses = [\"Draft\", \"In Review\", \"Accepted\", \"Rejected\"]\n\n[lifecycle.transitions]\nDraft = [\"In Review\"]\n\"In Review\" = [\"Accepted\", \"Rejected\"]\nRejected = []\n",
+        )
+        .unwrap();
+        let lc = load_project(&toml).unwrap().lifecycle;
+        assert_eq!(lc.resolve("in review"), Some("In Review"));
+        assert_eq!(lc.resolve("Proposed"), None);
+        assert!(lc.allows("Draft", "in review"));
+        assert!(!lc.allows("Draft", "Accepted"));
+        assert!(!lc.allows("Rejected", "Accepted"));
+        assert!(lc.allows("Rejected", "Rejected"));
+        // Statuses without transitions are unrestricted
+        assert!(lc.allows("Accepted", "Draft"));
+    }
+
+    #[test]
+    fn test_discovery_walks_up_to_repository_root() {
+        let dir = tempdir().unwrap();
+        let repo = dir.path().join("repo");
+        let nested = repo.join("src").join("deep");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+        // Above the repository root, so never picked
+        std::fs::write(dir.path().join("radr.toml"), "adr_dir = 'outer'\n").unwrap();
+        assert_eq!(discover_config(&nested), None);
+
+        std::fs::write(repo.join(".radrrc.yaml"), "adr_dir: adrs\n").unwrap();
+        let found = discover_config(&nested).unwrap();
+        assert_eq!(found, Path::new("../../.radrrc.yaml"));
+        assert!(nested.join(&found).is_file());
+        // The closest config wins
+        std::fs::write(repo.join("src").join("radr.json"), "{}").unwrap();
+        assert_eq!(discover_config(&nested).unwrap(), Path::new("../radr.json"));
+    }
+
+    #[test]
+    fn test_layers_override_in_order_and_record_origins() {
+        let dir = tempdir().unwrap();
+        let user_dir = dir.path().join("home").join("radr");
+        let project = dir.path().join("project");
+        std::fs::create_dir_all(user_dir.join("templates")).unwrap();
+        std::fs::create_dir_all(&project).unwrap();
+        std::fs::write(user_dir.join("templates").join("adr.md"), "T").unwrap();
+        let user = user_dir.join("config.toml");
+        std::fs::write(
+            &user,
+            "author = 'Ada'\ntemplate = 'templates/adr.md'\nadr_dir = 'decisions'\nformat = 'mdx'\n",
+        )
+        .unwrap();
+        let radr_toml = project.join("radr.toml");
+        std::fs::write(&radr_toml, "format = 'md'\nfront_matter = true\n").unwrap();
+        let env = |var: &str| match var {
+            "RADR_FRONT_MATTER" => Some("off".to_string()),
+            "RADR_LAYOUT" => Some("MADR".to_string()),
+            _ => None,
+        };
+        let overrides = vec!["index_name=ADRS.md".to_string()];
+
+        let layers = layered_config(Some(&user), Some(&radr_toml), env, &overrides).unwrap();
+        assert!(layers.problems.is_empty(), "{:?}", layers.problems);
+        let cfg = &layers.config;
+        assert_eq!(cfg.author.as_deref(), Some("Ada"));
+        // Templates belong to the user config, the ADR directory to the project
+        assert_eq!(
+            cfg.template,
+            Some(user_dir.join("templates").join("adr.md"))
+        );
+        assert_eq!(cfg.adr_dir, project.join("decisions"));
+        assert_eq!(cfg.format, "md");
+        assert!(!cfg.front_matter);
+        assert!(cfg.is_madr());
+        assert_eq!(cfg.index_name, "ADRS.md");
+        assert_eq!(layers.origins["author"], Origin::User(user.clone()));
+        assert_eq!(layers.origins["adr_dir"], Origin::User(user.clone()));
+        assert_eq!(layers.origins["format"], Origin::Project(radr_toml.clone()));
+        assert_eq!(
+            layers.origins["front_matter"],
+            Origin::Env("RADR_FRONT_MATTER".into())
+        );
+        assert_eq!(
+            layers.origins["index_name"],
+            Origin::Cli("index_name=ADRS.md".into())
+        );
+        assert_eq!(layers.origins["index_graph"], Origin::Default);
+        assert_eq!(
+            layers.origins["index_name"].to_string(),
+            "--set index_name=ADRS.md"
+        );
+
+        let env = |var: &str| match var {
+            "RADR_FORMAT" => Some("rst".to_string()),
+            "RADR_INDEX_GRAPH" => Some("maybe".to_string()),
+            _ => None,
+        };
+        let overrides: Vec<String> = ["formt=mdx", "lifecycle.statuses=Draft", "mdx"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let layers = layered_config(None, Some(&radr_toml), env, &overrides).unwrap();
+        let problems: Vec<String> = layers
+            .problems
+            .iter()
+            .map(|(o, p)| format!("{}: {}", o, p))
+            .collect();
+        assert_eq!(
+            problems,
+            [
+                "RADR_FORMAT: format must be one of md, mdx, not 'rst'",
+                "RADR_INDEX_GRAPH: index_graph must be true or false, not 'maybe'",
+                "--set formt=mdx: unknown key 'formt' (did you mean 'format'?)",
+                "--set lifecycle.statuses=Draft: lifecycle can only be set by editing the config file",
+                "--set mdx: expected KEY=VALUE, e.g. format=mdx",
+            ]
+        );
+        assert_eq!(layers.config.format, "md");
+        let err = layers.check().unwrap_err().to_string();
+        assert!(err.contains("Invalid config at RADR_FORMAT:\n  - format must be one of md, mdx"));
+    }
+
+    #[test]
+    fn test_set_keeps_format_comments_and_order() {
+        let dir = tempdir().unwrap();
+        let cfg = Config {
+            adr_dir: PathBuf::from("adrs"),
+            ..Config::default()
+        };
+        let toml = dir.path().join("radr.toml");
+        std::fs::write(&toml, render_config(&cfg, ConfigFormat::Toml)).unwrap();
+        std::fs::write(
+            &toml,
+            std::fs::read_to_string(&toml)
+                .unwrap()
+                .replace("format = \"md\"", "format = \"md\" # or mdx"),
+        )
+        .unwrap();
+        assert_eq!(set_config_value(&toml, "format", "MDX").unwrap(), "mdx");
+        assert_eq!(
+            set_config_value(&toml, "index_graph", "yes").unwrap(),
+            Value::Bool(true)
+        );
+        set_config_value(&toml, "author", "Ada").unwrap();
+        let written = std::fs::read_to_string(&toml).unwrap();
+        assert!(written.starts_with("# radr configuration."));
+        assert!(written.contains("\nformat = \"mdx\" # or mdx\n"));
+        // Commented-out settings are uncommented where they are
+        assert!(written
+            .contains("# Embed a Mermaid supersede graph in the index\nindex_graph = true\n"));
+        assert!(written.contains("\nauthor = \"Ada\"\n"));
+        let loaded = load_project(&toml).unwrap();
+        assert_eq!(loaded.format, "mdx");
+        assert!(loaded.index_graph);
+
+        let yaml = dir.path().join("radr.yaml");
+        std::fs::write(&yaml, "# ADRs\nadr_dir: adrs  # relative\nformat: md\n").unwrap();
+        set_config_value(&yaml, "adr_dir", "docs/decisions").unwrap();
+        set_config_value(&yaml, "front_matter", "true").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&yaml).unwrap(),
+            "# ADRs\nadr_dir: docs/decisions  # relative\nformat: md\nfront_matter: true\n"
+        );
+
+        let json = dir.path().join("radr.json");
+        std::fs::write(&json, "{\"format\": \"md\", \"adr_dir\": \"adrs\"}").unwrap();
+        set_config_value(&json, "format", "mdx").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&json).unwrap(),
+            "{\n  \"format\": \"mdx\",\n  \"adr_dir\": \"adrs\"\n}\n"
+        );
+
+        // Invalid settings leave the file alone
+        let err = set_config_value(&yaml, "formt", "mdx").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown key 'formt' (did you mean 'format'?)"
+        );
+        assert!(set_config_value(&yaml, "layout", "nygard").is_err());
+        let err = set_config_value(&yaml, "template", "missing.md").unwrap_err();
+        assert!(err.to_string().contains("it was left unchanged"));
+        assert!(!std::fs::read_to_string(&yaml).unwrap().contains("template"));
+    }
+
+    #[test]
+    fn test_get_reads_effective_values() {
+        let layers = layered_config(None, None, |_| None, &["format=mdx".to_string()]).unwrap();
+        let (value, origin) = layers.get("format").unwrap();
+        assert_eq!(value, "mdx");
+        assert_eq!(origin, Origin::Cli("format=mdx".into()));
+        let (statuses, origin) = layers.get("lifecycle.statuses").unwrap();
+        assert_eq!(statuses[0], "Proposed");
+        assert_eq!(origin, Origin::Default);
+        assert_eq!(
+            layers.get("lifecycle.status").unwrap_err().to_string(),
+            "unknown key 'lifecycle.status' (did you mean 'lifecycle.statuses'?)"
+        );
+    }
+
+    #[test]
+    fn test_values_follow_config_keys() {
+        let keys: Vec<&str> = Config::default().values().iter().map(|(k, _)| *k).collect();
+        let expected: Vec<&str> = KEYS.iter().map(|(k, _)| *k).collect();
+        assert_eq!(keys, expected);
+    }
+
+    #[test]
+    fn test_rendered_config_loads_back() {
+        let dir = tempdir().unwrap();
+        let cfg = Config {
+            adr_dir: PathBuf::from("adrs"),
+            format: "mdx".into(),
+            front_matter: true,
+            ..Config::default()
+        };
+        for format in [ConfigFormat::Toml, ConfigFormat::Yaml, ConfigFormat::Json] {
+            let path = dir.path().join(format.file_name());
+            let rendered = render_config(&cfg, format);
+            std::fs::write(&path, &rendered).unwrap();
+            let loaded = load_project(&path).unwrap();
+            assert_eq!(loaded.adr_dir, dir.path().join("adrs"));
+            assert_eq!(loaded.format, "mdx");
+            assert!(loaded.front_matter);
+            assert_eq!(loaded.layout, "classic");
+            assert_eq!(loaded.lifecycle, Lifecycle::default());
+            if format != ConfigFormat::Json {
+                assert!(rendered.contains("# Layout of new ADRs"));
+            }
+        }
+        let toml = render_config(&cfg, ConfigFormat::Toml);
+        assert!(toml.contains("\nadr_dir = \"adrs\"\n"));
+        assert!(toml.contains("\n# index_tags = \"none\"\n"));
+    }
+
+    #[test]
+    fn test_lifecycle_rejects_unknown_transition_status() {
+        let dir = tempdir().unwrap();
+        let yaml = dir.path().join("radr.yaml");
+        std::fs::write(
+            &yaml,
+            "lifecycle:\n  statuses: [Proposed, Accepted]\n  transitions:\n    Proposed: [Acepted]\n",
+        )
+        .unwrap();
+        let err = load_project(&yaml).unwrap_err();
+        assert!(err.to_string().contains("mentions 'Acepted'"));
+    }
 }
@@ -0,0 +1,794 @@
+use serde_yaml::Value;
+
+use crate::domain::{parse_adr_ref, parse_adr_refs, parse_list, LinkKind, Role, StatusChange};
+use crate::yaml_util::{
+    has_yaml_field, remove_yaml_field, set_yaml_entry, set_yaml_field, yaml_scalar,
+};
+
+/// Metadata keys written as `Key: value` lines, in the order radr renders them.
+pub const META_KEYS: &[&str] = &[
+    "Title",
+    "Date",
+    "Status",
+    "Superseded-by",
+    "Supersedes",
+    "Deprecation-reason",
+    "Amends",
+    "Amended-by",
+    "Relates-to",
+    "Depends-on",
+    "Required-by",
+    "Conflicts-with",
+    "Tags",
+    "Authors",
+    "Deciders",
+    "Consulted",
+    "Informed",
+];
+
+/// Heading of the section logging status changes when metadata lives in the body.
+pub const HISTORY_SECTION: &str = "Status History";
+
+/// A `## Heading` section of an ADR body. Text before the first heading forms a
+/// section without heading.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    heading: Option<String>,
+    lines: Vec<String>,
+}
+
+impl Section {
+    /// Heading text without the `## ` marker, if the section has one.
+    pub fn name(&self) -> Option<&str> {
+        self.heading
+            .as_deref()
+            .map(|h| h.trim_start_matches('#').trim())
+    }
+
+    /// Section text below the heading, as written in the file.
+    pub fn content(&self) -> String {
+        self.lines.concat()
+    }
+
+    /// The section as written in the file, heading included.
+    pub fn render(&self) -> String {
+        let mut out = self.heading.clone().unwrap_or_default();
+        out.push_str(&self.content());
+        out
+    }
+}
+
+/// An ADR file split into front matter, title heading, metadata block and body
+/// sections. `render` reproduces the parsed text byte-for-byte; edits only touch
+/// the parts they name.
+///
+/// The metadata block is the run of `Key: value` and blank lines that directly
+/// follows the title heading (or the front matter when there is no heading). It
+/// ends at the first other line, so `Status:` examples in the body (fenced code,
+/// block quotes, tables) are never read or rewritten as metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdrDocument {
+    /// YAML between the `---` delimiters
+    front_matter: Option<String>,
+    /// A leading `---` line without closing delimiter; the file is left alone
+    unclosed_front_matter: bool,
+    /// Blank lines before the heading or metadata block
+    lead: Vec<String>,
+    /// The `# ...` title line
+    heading: Option<String>,
+    /// `Key: value` and blank lines under the heading
+    meta: Vec<String>,
+    sections: Vec<Section>,
+}
+
+impl AdrDocument {
+    pub fn parse(raw: &str) -> Self {
+        let (front_matter, rest) = match split_front_matter(raw) {
+            Some((fm, rest)) => (Some(fm.to_string()), rest),
+            None => (None, raw),
+        };
+        let unclosed_front_matter = front_matter.is_none() && raw.starts_with("---\n");
+        let lines: Vec<&str> = rest.split_inclusive('\n').collect();
+        let mut i = 0usize;
+
+        let mut lead = Vec::new();
+        while i < lines.len() && lines[i].trim().is_empty() {
+            lead.push(lines[i].to_string());
+            i += 1;
+        }
+        let mut heading = None;
+        if i < lines.len() && lines[i].starts_with("# ") {
+            heading = Some(lines[i].to_string());
+            i += 1;
+        }
+        let mut meta = Vec::new();
+        if !unclosed_front_matter {
+            while i < lines.len() && (lines[i].trim().is_empty() || header_key(lines[i]).is_some())
+            {
+                meta.push(lines[i].to_string());
+                i += 1;
+            }
+        }
+
+        let mut sections = Vec::new();
+        let mut current = Section {
+            heading: None,
+            lines: Vec::new(),
+        };
+        // `## ` lines inside fenced code are content, not section headings
+        let mut fence: Option<&str> = None;
+        for line in &lines[i..] {
+            let trimmed = line.trim_start();
+            if let Some(marker) = fence {
+                if trimmed.starts_with(marker) {
+                    fence = None;
+                }
+            } else if trimmed.starts_with("```") {
+                fence = Some("```");
+            } else if trimmed.starts_with("~~~") {
+                fence = Some("~~~");
+            }
+            if fence.is_none() && line.starts_with("## ") {
+                if current.heading.is_some() || !current.lines.is_empty() {
+                    sections.push(current);
+                }
+                current = Section {
+                    heading: Some(line.to_string()),
+                    lines: Vec::new(),
+                };
+            } else {
+                current.lines.push(line.to_string());
+            }
+        }
+        if current.heading.is_some() || !current.lines.is_empty() {
+            sections.push(current);
+        }
+
+        Self {
+            front_matter,
+            unclosed_front_matter,
+            lead,
+            heading,
+            meta,
+            sections,
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        if let Some(fm) = &self.front_matter {
+            out.push_str("---\n");
+            out.push_str(fm);
+            out.push_str("\n---\n");
+        }
+        out.push_str(&self.lead.concat());
+        if let Some(h) = &self.heading {
+            out.push_str(h);
+        }
+        out.push_str(&self.meta_text());
+        out.push_str(&self.body());
+        out
+    }
+
+    /// Raw YAML of the front matter block, without delimiters.
+    pub fn front_matter(&self) -> Option<&str> {
+        self.front_matter.as_deref()
+    }
+
+    /// The front matter parsed as YAML.
+    pub fn front_matter_yaml(&self) -> Option<serde_yaml::Result<Value>> {
+        self.front_matter
+            .as_deref()
+            .map(serde_yaml::from_str::<Value>)
+    }
+
+    /// Text of the title heading without the `# ` marker.
+    pub fn heading(&self) -> Option<&str> {
+        self.heading.as_deref().map(|h| h[2..].trim())
+    }
+
+    /// Title from the heading, with a classic `ADR NNNN: ` prefix removed.
+    pub fn title(&self) -> Option<&str> {
+        let h = self.heading()?;
+        match h.split_once(": ") {
+            Some((head, t)) if head.starts_with("ADR ") => Some(t.trim()),
+            _ => Some(h),
+        }
+    }
+
+    /// True for a classic `# ADR NNNN: Title` heading.
+    pub fn has_classic_heading(&self) -> bool {
+        self.heading().is_some_and(|h| h.starts_with("ADR "))
+    }
+
+    /// `(key, value)` pairs of the metadata block, in file order.
+    pub fn fields(&self) -> Vec<(&str, &str)> {
+        self.meta
+            .iter()
+            .filter_map(|l| {
+                let key = header_key(l)?;
+                Some((key, l[key.len() + 1..].trim()))
+            })
+            .collect()
+    }
+
+    /// Value of the first `key: value` line in the metadata block.
+    pub fn field(&self, key: &str) -> Option<&str> {
+        self.fields()
+            .into_iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v)
+    }
+
+    /// The metadata block as written in the file.
+    pub fn meta_text(&self) -> String {
+        self.meta.concat()
+    }
+
+    pub fn sections(&self) -> &[Section] {
+        &self.sections
+    }
+
+    /// Finds a section by heading, ignoring case.
+    pub fn section(&self, name: &str) -> Option<&Section> {
+        self.sections
+            .iter()
+            .find(|s| s.name().is_some_and(|n| n.eq_ignore_ascii_case(name)))
+    }
+
+    /// Everything after the metadata block, as written in the file.
+    pub fn body(&self) -> String {
+        self.sections.iter().map(Section::render).collect()
+    }
+
+    /// Sets metadata fields. When the front matter carries `status` or `date`
+    /// (MADR, full front matter) the YAML fields are set; otherwise `Key: value`
+    /// lines in the metadata block are replaced in place, or inserted after the last
+    /// field that precedes them in `META_KEYS` order (or before the first one).
+    /// `Superseded-by` is kept right under `Status`. A file with an unclosed front
+    /// matter block is left untouched.
+    pub fn set_fields(&mut self, fields: &[(&str, String)]) {
+        if self.unclosed_front_matter {
+            return;
+        }
+        if let Some(fm) = self.metadata_front_matter() {
+            self.front_matter = Some(set_front_matter_fields(fm, fields));
+            return;
+        }
+
+        for (key, value) in fields {
+            let mut found = false;
+            for l in self.meta.iter_mut() {
+                if meta_key(l) == Some(*key) {
+                    let eol = &l[l.trim_end_matches(['\n', '\r']).len()..];
+                    *l = format!("{}: {}{}", key, value, eol);
+                    found = true;
+                }
+            }
+            if found {
+                continue;
+            }
+            let rank = meta_rank(key);
+            let present: Vec<(usize, usize)> = self
+                .meta
+                .iter()
+                .enumerate()
+                .filter_map(|(i, l)| meta_key(l).map(|k| (i, meta_rank(k))))
+                .collect();
+            let insert_at = if let Some((i, _)) = present.iter().rev().find(|(_, r)| *r < rank) {
+                i + 1
+            } else if let Some((i, _)) = present.first() {
+                *i
+            } else {
+                self.meta
+                    .iter()
+                    .position(|l| !l.trim().is_empty())
+                    .unwrap_or(self.meta.len())
+            };
+            self.terminate_line_before(insert_at);
+            self.meta.insert(insert_at, format!("{}: {}\n", key, value));
+        }
+
+        // Ensure Superseded-by appears immediately after Status
+        let idx_status = self.meta.iter().position(|l| meta_key(l) == Some("Status"));
+        let idx_superseded_by = self
+            .meta
+            .iter()
+            .position(|l| meta_key(l) == Some("Superseded-by"));
+        if let (Some(s_idx), Some(sb_idx)) = (idx_status, idx_superseded_by) {
+            if sb_idx != s_idx + 1 {
+                let sb_line = self.meta.remove(sb_idx);
+                let insert_pos = if sb_idx < s_idx { s_idx } else { s_idx + 1 };
+                self.meta.insert(insert_pos, sb_line);
+            }
+        }
+
+        // Keep the block separated from the front matter and the body
+        if self.front_matter.is_some() && self.heading.is_none() && self.lead.is_empty() {
+            self.lead.push("\n".to_string());
+        }
+        let ends_blank = self.meta.last().is_some_and(|l| l.trim().is_empty());
+        if !ends_blank && !self.sections.is_empty() {
+            self.meta.push("\n".to_string());
+        }
+    }
+
+    /// Status changes, oldest first: the `history` front matter list when metadata
+    /// lives in front matter, else the items of the `## Status History` section.
+    pub fn history(&self) -> Vec<StatusChange> {
+        if self.metadata_front_matter().is_some() {
+            return match self.front_matter_yaml() {
+                Some(Ok(Value::Mapping(map))) => map
+                    .get("history")
+                    .and_then(|v| serde_yaml::from_value(v.clone()).ok())
+                    .unwrap_or_default(),
+                _ => Vec::new(),
+            };
+        }
+        self.section(HISTORY_SECTION)
+            .map(|s| {
+                s.lines
+                    .iter()
+                    .filter_map(|l| StatusChange::parse_line(l))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Appends a status change to the history, creating the `history` list or the
+    /// `## Status History` section at the end of the file when missing. A file with
+    /// an unclosed front matter block is left untouched.
+    pub fn push_history(&mut self, change: StatusChange) {
+        if self.unclosed_front_matter {
+            return;
+        }
+        if let Some(fm) = self.metadata_front_matter() {
+            let mut history = self.history();
+            history.push(change);
+            self.front_matter = Some(set_yaml_entry(fm, "history", &history_yaml(&history)));
+            return;
+        }
+        let line = format!("{}\n", change.to_line());
+        let existing = self.sections.iter_mut().find(|s| {
+            s.name()
+                .is_some_and(|n| n.eq_ignore_ascii_case(HISTORY_SECTION))
+        });
+        if let Some(section) = existing {
+            match section.lines.iter().rposition(|l| !l.trim().is_empty()) {
+                Some(i) => {
+                    terminate(&mut section.lines[i]);
+                    section.lines.insert(i + 1, line);
+                }
+                None => {
+                    if let Some(h) = section.heading.as_mut() {
+                        terminate(h);
+                    }
+                    section.lines = vec!["\n".to_string(), line];
+                }
+            }
+            return;
+        }
+        self.end_with_blank_line();
+        self.sections.push(Section {
+            heading: Some(format!("## {}\n", HISTORY_SECTION)),
+            lines: vec!["\n".to_string(), line],
+        });
+    }
+
+    /// True when radr's metadata, history included, lives in front matter (MADR,
+    /// full front matter) rather than in the body.
+    pub fn has_metadata_front_matter(&self) -> bool {
+        self.metadata_front_matter().is_some()
+    }
+
+    /// The front matter block when it holds radr's metadata (`status` or `date`).
+    fn metadata_front_matter(&self) -> Option<&str> {
+        self.front_matter
+            .as_deref()
+            .filter(|fm| has_yaml_field(fm, "status") || has_yaml_field(fm, "date"))
+    }
+
+    /// Makes sure the document ends with a blank line, so a section can follow.
+    fn end_with_blank_line(&mut self) {
+        let lines = match self.sections.last_mut() {
+            Some(section) => {
+                if section.lines.is_empty() {
+                    if let Some(h) = section.heading.as_mut() {
+                        terminate(h);
+                    }
+                }
+                &mut section.lines
+            }
+            None => {
+                if self.meta.is_empty() {
+                    match self.heading.as_mut() {
+                        Some(h) => terminate(h),
+                        None if self.front_matter.is_some() && self.lead.is_empty() => {
+                            self.lead.push("\n".to_string());
+                            return;
+                        }
+                        None => return,
+                    }
+                }
+                &mut self.meta
+            }
+        };
+        match lines.last_mut() {
+            Some(l) if l.trim().is_empty() => terminate(l),
+            Some(l) => {
+                terminate(l);
+                lines.push("\n".to_string());
+            }
+            None => lines.push("\n".to_string()),
+        }
+    }
+
+    /// Removes a metadata field: the front matter entry (`Amended-by` maps to
+    /// `amended_by`) when metadata lives in front matter, else its `Key: value`
+    /// lines.
+    pub fn remove_field(&mut self, key: &str) {
+        if self.unclosed_front_matter {
+            return;
+        }
+        if let Some(fm) = self.metadata_front_matter() {
+            let yaml_key = key.to_ascii_lowercase().replace('-', "_");
+            self.front_matter = Some(remove_yaml_field(fm, &yaml_key));
+            return;
+        }
+        self.meta.retain(|l| header_key(l) != Some(key));
+    }
+
+    /// Sets the tags (removes them when empty). Tags go to front matter whenever the
+    /// file has one, since site generators read them there, else to a `Tags:` line.
+    pub fn set_tags(&mut self, tags: &[String]) {
+        if self.unclosed_front_matter {
+            return;
+        }
+        match &self.front_matter {
+            Some(fm) if tags.is_empty() => self.front_matter = Some(remove_yaml_field(fm, "tags")),
+            Some(fm) => self.front_matter = Some(set_yaml_field(fm, "tags", &yaml_list(tags))),
+            None if tags.is_empty() => self.remove_field("Tags"),
+            None => self.set_fields(&[("Tags", tags.join(", "))]),
+        }
+    }
+
+    /// Makes sure the line preceding metadata index `idx` ends with a newline.
+    fn terminate_line_before(&mut self, idx: usize) {
+        let prev = if idx > 0 {
+            self.meta.get_mut(idx - 1)
+        } else {
+            self.heading.as_mut().or(self.lead.last_mut())
+        };
+        if let Some(l) = prev {
+            terminate(l);
+        }
+    }
+}
+
+fn terminate(line: &mut String) {
+    if !line.ends_with('\n') {
+        line.push('\n');
+    }
+}
+
+/// YAML flow sequence of `items`, e.g. `[database, security]`.
+pub(crate) fn yaml_list(items: &[String]) -> String {
+    format!(
+        "[{}]",
+        items
+            .iter()
+            .map(|i| yaml_scalar(i))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+/// ADR numbers as a front matter value: `3` for one, `[3, 5]` for several.
+pub(crate) fn yaml_refs(numbers: &[u32]) -> String {
+    match numbers {
+        [n] => n.to_string(),
+        _ => format!(
+            "[{}]",
+            numbers
+                .iter()
+                .map(u32::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// The `history` front matter entry listing `history`.
+pub(crate) fn history_yaml(history: &[StatusChange]) -> String {
+    let mut out = String::from("history:\n");
+    for c in history {
+        out.push_str(&format!("- date: {}\n", c.date));
+        out.push_str(&format!("  status: {}\n", yaml_scalar(&c.status)));
+        if let Some(by) = &c.by {
+            out.push_str(&format!("  by: {}\n", yaml_scalar(by)));
+        }
+    }
+    out
+}
+
+/// The `## Status History` section listing `history`.
+pub(crate) fn history_section(history: &[StatusChange]) -> String {
+    let mut out = format!("## {}\n\n", HISTORY_SECTION);
+    for c in history {
+        out.push_str(&c.to_line());
+        out.push('\n');
+    }
+    out
+}
+
+/// Splits off a leading `---` front matter block, returning the block (without
+/// delimiters) and the rest of the file.
+pub(crate) fn split_front_matter(content: &str) -> Option<(&str, &str)> {
+    let stripped = content.strip_prefix("---\n")?;
+    let end = stripped.find("\n---\n")?;
+    Some((&stripped[..end], &stripped[end + 5..]))
+}
+
+/// Key of a `Key: value` header line such as `Status: Accepted` or
+/// `Deciders: Alice`.
+pub(crate) fn header_key(line: &str) -> Option<&str> {
+    let (key, _) = line.split_once(':')?;
+    let mut chars = key.chars();
+    let valid = chars.next()?.is_ascii_uppercase()
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    valid.then_some(key)
+}
+
+/// The radr-managed key of a metadata line, if any.
+pub(crate) fn meta_key(line: &str) -> Option<&'static str> {
+    META_KEYS
+        .iter()
+        .copied()
+        .find(|k| line.strip_prefix(k).is_some_and(|r| r.starts_with(':')))
+}
+
+fn meta_rank(key: &str) -> usize {
+    META_KEYS.iter().position(|k| *k == key).unwrap_or(0)
+}
+
+/// Applies `Key: value` metadata to a front matter block, using the YAML field
+/// names radr parses (`date`, `status`, `superseded_by`, `supersedes`, ...).
+fn set_front_matter_fields(fm: &str, fields: &[(&str, String)]) -> String {
+    let mut fm = fm.to_string();
+    for (key, value) in fields {
+        let yaml_key = key.to_ascii_lowercase().replace('-', "_");
+        let yaml_value = match *key {
+            "Date" => value.clone(),
+            k if k == "Supersedes" || LinkKind::ALL.iter().any(|l| l.header_key() == k) => {
+                match parse_adr_refs(value) {
+                    refs if refs.is_empty() => yaml_scalar(value),
+                    refs => yaml_refs(&refs),
+                }
+            }
+            k if k == "Tags" || Role::ALL.iter().any(|r| r.header_key() == k) => {
+                yaml_list(&parse_list(value))
+            }
+            "Superseded-by" => parse_adr_ref(value)
+                .map(|n| n.to_string())
+                .unwrap_or_else(|| yaml_scalar(value)),
+            _ => yaml_scalar(value),
+        };
+        fm = set_yaml_field(&fm, &yaml_key, &yaml_value);
+    }
+    fm
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLES: &[&str] = &[
+        "# ADR 0001: Use X\n\nDate: 2024-01-01\nStatus: Accepted\n\n## Context\n\nWhy.\n\n## Decision\n\nX.\n",
+        "---\ntitle: Use X\n---\n\nDate: 2024-01-01\r\nStatus: Accepted\r\n\r\nBody without heading",
+        "---\nstatus: Proposed\ndate: 2024-01-01\n---\n\n# Use X\n\n## Context and Problem Statement\n",
+        "---\nunclosed: true\n\n# Heading\n",
+        "",
+        "\n\n# Only heading",
+    ];
+
+    #[test]
+    fn renders_byte_for_byte() {
+        for raw in SAMPLES {
+            assert_eq!(AdrDocument::parse(raw).render(), *raw);
+        }
+    }
+
+    #[test]
+    fn exposes_heading_fields_and_sections() {
+        let doc = AdrDocument::parse(SAMPLES[0]);
+        assert_eq!(doc.title(), Some("Use X"));
+        assert!(doc.has_classic_heading());
+        assert_eq!(
+            doc.fields(),
+            vec![("Date", "2024-01-01"), ("Status", "Accepted")]
+        );
+        assert_eq!(doc.section("decision").unwrap().content(), "\nX.\n");
+        assert_eq!(
+            doc.sections()
+                .iter()
+                .map(|s| s.name().unwrap())
+                .collect::<Vec<_>>(),
+            vec!["Context", "Decision"]
+        );
+
+        let madr = AdrDocument::parse(SAMPLES[2]);
+        assert_eq!(madr.title(), Some("Use X"));
+        assert!(!madr.has_classic_heading());
+        assert!(madr.fields().is_empty());
+    }
+
+    #[test]
+    fn set_fields_replaces_and_inserts_in_order() {
+        let mut doc = AdrDocument::parse("# ADR 0001: Old\n\nContext\n");
+        doc.set_fields(&[
+            ("Superseded-by", "0002".to_string()),
+            ("Status", "Superseded by 0002".to_string()),
+        ]);
+        assert_eq!(
+            doc.render(),
+            "# ADR 0001: Old\n\nStatus: Superseded by 0002\nSuperseded-by: 0002\n\nContext\n"
+        );
+
+        let mut doc = AdrDocument::parse(SAMPLES[1]);
+        doc.set_fields(&[("Status", "Rejected".to_string())]);
+        assert_eq!(
+            doc.render(),
+            "---\ntitle: Use X\n---\n\nDate: 2024-01-01\r\nStatus: Rejected\r\n\r\nBody without heading"
+        );
+
+        doc.remove_field("Status");
+        assert_eq!(
+            doc.render(),
+            "---\ntitle: Use X\n---\n\nDate: 2024-01-01\r\n\r\nBody without heading"
+        );
+
+        let mut doc = AdrDocument::parse("# ADR 0003: Bare");
+        doc.set_fields(&[("Date", "2024-01-01".to_string())]);
+        assert_eq!(doc.render(), "# ADR 0003: Bare\nDate: 2024-01-01\n");
+
+        let mut doc = AdrDocument::parse(SAMPLES[3]);
+        doc.set_fields(&[("Status", "Accepted".to_string())]);
+        assert_eq!(doc.render(), SAMPLES[3]);
+    }
+
+    #[test]
+    fn set_fields_targets_front_matter_fields() {
+        let mut doc =
+            AdrDocument::parse("---\nstatus: Proposed\ndate: 2024-01-01\n---\n\n# T\n\nBody\n");
+        doc.set_fields(&[
+            ("Status", "Superseded by 0003".to_string()),
+            ("Superseded-by", "0003".to_string()),
+            ("Supersedes", "[0001](0001-a.md)".to_string()),
+        ]);
+        assert_eq!(
+            doc.render(),
+            "---\nstatus: Superseded by 0003\ndate: 2024-01-01\nsuperseded_by: 3\nsupersedes: 1\n---\n\n# T\n\nBody\n"
+        );
+        doc.set_fields(&[("Supersedes", "[0001](0001-a.md), 0002".to_string())]);
+        assert!(doc.render().contains("\nsupersedes: [1, 2]\n"));
+        doc.remove_field("Superseded-by");
+        assert!(!doc.render().contains("superseded_by"));
+        // Title-only front matter keeps metadata in the body
+        let mut doc = AdrDocument::parse("---\ntitle: T\n---\n\nStatus: Proposed\n");
+        doc.set_fields(&[("Status", "Accepted".to_string())]);
+        assert_eq!(doc.render(), "---\ntitle: T\n---\n\nStatus: Accepted\n");
+        // ... except tags, which stay in front matter
+        doc.set_tags(&["db".to_string(), "2024".to_string()]);
+        assert!(doc
+            .render()
+            .starts_with("---\ntitle: T\ntags: [db, \"2024\"]\n---\n"));
+        doc.set_tags(&[]);
+        assert_eq!(doc.render(), "---\ntitle: T\n---\n\nStatus: Accepted\n");
+    }
+
+    #[test]
+    fn body_examples_stay_out_of_metadata_and_sections() {
+        let raw = "# ADR 0001: X\n\nStatus: Proposed\n\n## Decision\n\n```yaml\n## not a section\nStatus: Accepted\n```\n\n> Status: Rejected\n\n| Field | Value |\n|---|---|\n| Status: | Deprecated |\n";
+        let mut doc = AdrDocument::parse(raw);
+        assert_eq!(doc.fields(), vec![("Status", "Proposed")]);
+        assert_eq!(doc.sections().len(), 1);
+        assert!(doc
+            .section("Decision")
+            .unwrap()
+            .content()
+            .contains("## not a section"));
+
+        doc.set_fields(&[("Status", "Accepted".to_string())]);
+        let out = doc.render();
+        assert!(out.starts_with("# ADR 0001: X\n\nStatus: Accepted\n\n## Decision"));
+        assert_eq!(out.matches("Status: Accepted").count(), 2);
+        assert!(out.contains("> Status: Rejected\n"));
+        assert!(out.contains("| Status: | Deprecated |\n"));
+    }
+
+    #[test]
+    fn push_history_appends_to_section_or_front_matter() {
+        let change = |date: &str, status: &str, by: Option<&str>| StatusChange {
+            date: date.into(),
+            status: status.into(),
+            by: by.map(str::to_string),
+        };
+        let mut doc = AdrDocument::parse("# ADR 0001: X\n\nStatus: Proposed\n\n## Context\n\nWhy.");
+        doc.push_history(change("2024-01-01", "Proposed", None));
+        doc.push_history(change("2024-02-01", "Accepted", Some("Ada")));
+        assert_eq!(
+            doc.render(),
+            "# ADR 0001: X\n\nStatus: Proposed\n\n## Context\n\nWhy.\n\n## Status History\n\n- 2024-01-01: Proposed\n- 2024-02-01: Accepted (by Ada)\n"
+        );
+        assert_eq!(
+            doc.history()[1],
+            change("2024-02-01", "Accepted", Some("Ada"))
+        );
+
+        let mut doc = AdrDocument::parse("# ADR 0002: Y\n\nStatus: Proposed\n");
+        doc.push_history(change("2024-01-01", "Proposed", None));
+        assert_eq!(
+            doc.render(),
+            "# ADR 0002: Y\n\nStatus: Proposed\n\n## Status History\n\n- 2024-01-01: Proposed\n"
+        );
+
+        let mut doc =
+            AdrDocument::parse("---\nstatus: Proposed\ndate: 2024-01-01\n---\n\n# T\n\nBody\n");
+        doc.push_history(change("2024-01-01", "Proposed", None));
+        doc.push_history(change("2024-02-01", "Accepted", Some("Ada")));
+        assert_eq!(
+            doc.render(),
+            "---\nstatus: Proposed\ndate: 2024-01-01\nhistory:\n- date: 2024-01-01\n  status: Proposed\n- date: 2024-02-01\n  status: Accepted\n  by: Ada\n---\n\n# T\n\nBody\n"
+        );
+        assert_eq!(doc.history().len(), 2);
+        assert!(doc.section(HISTORY_SECTION).is_none());
+
+        let mut doc = AdrDocument::parse("---\nstatus: Proposed\n---\n\n# T\n");
+        for by in ["#arch-team", "[ops] Ada", "null"] {
+            doc.push_history(change("2024-02-01", "Accepted", Some(by)));
+        }
+        let parsed = AdrDocument::parse(&doc.render());
+        let by: Vec<_> = parsed.history().into_iter().map(|c| c.by).collect();
+        assert_eq!(
+            by,
+            ["#arch-team", "[ops] Ada", "null"].map(|b| Some(b.to_string()))
+        );
+    }
+
+    #[test]
+    fn yaml_list_round_trips_special_items() {
+        let items: Vec<String> = [
+            "database",
+            "#ops",
+            "a,b",
+            "[x]",
+            "{y}",
+            "&anchor",
+            "*alias",
+            "true",
+            "null",
+            "~",
+            "2024",
+            "1.5",
+            "- item",
+            "key: value",
+            "",
+            "say \"hi\"",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let list = yaml_list(&items);
+        assert!(list.starts_with("[database, \"#ops\", "));
+        let parsed: Vec<String> = serde_yaml::from_str(&list).unwrap();
+        assert_eq!(parsed, items);
+
+        let mut doc = AdrDocument::parse("---\ntitle: T\n---\n\n# T\n");
+        doc.set_tags(&items[..10]);
+        let Some(Ok(fm)) = doc.front_matter_yaml() else {
+            panic!("front matter should stay valid YAML");
+        };
+        let tags: Vec<String> = serde_yaml::from_value(fm["tags"].clone()).unwrap();
+        assert_eq!(tags, items[..10]);
+    }
+}
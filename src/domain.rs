@@ -1,16 +1,258 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct AdrMeta {
     pub number: u32,
     pub title: String,
     pub status: String,
     pub date: String,
-    pub supersedes: Option<u32>,
+    /// Date the ADR was proposed: the first history entry, else `date`
+    pub created: String,
+    /// Date of the latest move to Accepted or Rejected, if any
+    pub decided: Option<String>,
+    /// Recorded status changes, oldest first
+    pub history: Vec<StatusChange>,
+    /// ADRs this one replaces
+    pub supersedes: Vec<u32>,
     pub superseded_by: Option<u32>,
+    /// Why the ADR was retired, set by `radr deprecate`
+    pub deprecation_reason: Option<String>,
+    /// Typed relations to other ADRs besides supersession, grouped by kind
+    pub links: Vec<AdrLink>,
+    /// Free-form categories such as `database` or `security`
+    pub tags: Vec<String>,
+    #[serde(flatten)]
+    pub people: People,
     pub path: PathBuf,
 }
 
+/// Role a person plays in a decision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Author,
+    Decider,
+    Consulted,
+    Informed,
+}
+
+impl Role {
+    pub const ALL: [Role; 4] = [Role::Author, Role::Decider, Role::Consulted, Role::Informed];
+
+    /// Metadata line key, e.g. `Deciders`.
+    pub fn header_key(self) -> &'static str {
+        match self {
+            Role::Author => "Authors",
+            Role::Decider => "Deciders",
+            Role::Consulted => "Consulted",
+            Role::Informed => "Informed",
+        }
+    }
+
+    /// Front matter key, e.g. `deciders`.
+    pub fn yaml_key(self) -> &'static str {
+        match self {
+            Role::Author => "authors",
+            Role::Decider => "deciders",
+            Role::Consulted => "consulted",
+            Role::Informed => "informed",
+        }
+    }
+}
+
+/// People involved in a decision, per role.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct People {
+    pub authors: Vec<String>,
+    pub deciders: Vec<String>,
+    pub consulted: Vec<String>,
+    pub informed: Vec<String>,
+}
+
+impl People {
+    pub fn get(&self, role: Role) -> &[String] {
+        match role {
+            Role::Author => &self.authors,
+            Role::Decider => &self.deciders,
+            Role::Consulted => &self.consulted,
+            Role::Informed => &self.informed,
+        }
+    }
+
+    pub fn get_mut(&mut self, role: Role) -> &mut Vec<String> {
+        match role {
+            Role::Author => &mut self.authors,
+            Role::Decider => &mut self.deciders,
+            Role::Consulted => &mut self.consulted,
+            Role::Informed => &mut self.informed,
+        }
+    }
+
+    /// This list of people with the names in `other` appended per role, skipping
+    /// names already listed (ignoring case).
+    pub fn merged(&self, other: &People) -> People {
+        let mut out = self.clone();
+        for role in Role::ALL {
+            let list = out.get_mut(role);
+            for name in other.get(role) {
+                if !list_contains(list, name) && !name.trim().is_empty() {
+                    list.push(name.trim().to_string());
+                }
+            }
+        }
+        out
+    }
+
+    /// True when `name` is listed in any role, ignoring case.
+    pub fn contains(&self, name: &str) -> bool {
+        Role::ALL.iter().any(|r| list_contains(self.get(*r), name))
+    }
+}
+
+/// Kind of relation between two ADRs. Every kind has a reciprocal written to the
+/// target ADR; `relates-to` and `conflicts-with` are their own reciprocal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum LinkKind {
+    Amends,
+    #[value(skip)]
+    AmendedBy,
+    #[value(name = "relates", alias = "relates-to")]
+    RelatesTo,
+    #[value(name = "depends", alias = "depends-on")]
+    DependsOn,
+    #[value(skip)]
+    RequiredBy,
+    #[value(name = "conflicts", alias = "conflicts-with")]
+    ConflictsWith,
+}
+
+impl LinkKind {
+    pub const ALL: [LinkKind; 6] = [
+        LinkKind::Amends,
+        LinkKind::AmendedBy,
+        LinkKind::RelatesTo,
+        LinkKind::DependsOn,
+        LinkKind::RequiredBy,
+        LinkKind::ConflictsWith,
+    ];
+
+    /// Key of the `Key: value` metadata line, e.g. `Amended-by`.
+    pub fn header_key(self) -> &'static str {
+        match self {
+            LinkKind::Amends => "Amends",
+            LinkKind::AmendedBy => "Amended-by",
+            LinkKind::RelatesTo => "Relates-to",
+            LinkKind::DependsOn => "Depends-on",
+            LinkKind::RequiredBy => "Required-by",
+            LinkKind::ConflictsWith => "Conflicts-with",
+        }
+    }
+
+    /// Front matter field name, e.g. `amended_by`.
+    pub fn yaml_key(self) -> &'static str {
+        match self {
+            LinkKind::Amends => "amends",
+            LinkKind::AmendedBy => "amended_by",
+            LinkKind::RelatesTo => "relates_to",
+            LinkKind::DependsOn => "depends_on",
+            LinkKind::RequiredBy => "required_by",
+            LinkKind::ConflictsWith => "conflicts_with",
+        }
+    }
+
+    /// The kind recorded on the other end of the relation.
+    pub fn reciprocal(self) -> LinkKind {
+        match self {
+            LinkKind::Amends => LinkKind::AmendedBy,
+            LinkKind::AmendedBy => LinkKind::Amends,
+            LinkKind::DependsOn => LinkKind::RequiredBy,
+            LinkKind::RequiredBy => LinkKind::DependsOn,
+            LinkKind::RelatesTo => LinkKind::RelatesTo,
+            LinkKind::ConflictsWith => LinkKind::ConflictsWith,
+        }
+    }
+
+    /// The relation as a phrase, e.g. `depends on`.
+    pub fn phrase(self) -> &'static str {
+        match self {
+            LinkKind::Amends => "amends",
+            LinkKind::AmendedBy => "is amended by",
+            LinkKind::RelatesTo => "relates to",
+            LinkKind::DependsOn => "depends on",
+            LinkKind::RequiredBy => "is required by",
+            LinkKind::ConflictsWith => "conflicts with",
+        }
+    }
+}
+
+/// A typed relation from one ADR to `target`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct AdrLink {
+    pub kind: LinkKind,
+    pub target: u32,
+}
+
+/// Targets of the `kind` relations in `links`, in file order.
+pub fn link_targets(links: &[AdrLink], kind: LinkKind) -> Vec<u32> {
+    links
+        .iter()
+        .filter(|l| l.kind == kind)
+        .map(|l| l.target)
+        .collect()
+}
+
+/// One entry of an ADR's status history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusChange {
+    pub date: String,
+    pub status: String,
+    /// Who made the change, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub by: Option<String>,
+}
+
+impl StatusChange {
+    /// Parses a `## Status History` list item such as
+    /// `- 2024-02-01: Accepted (by Alice)`.
+    pub fn parse_line(line: &str) -> Option<Self> {
+        let item = line.trim().strip_prefix("- ")?;
+        let (date, rest) = item.split_once(": ")?;
+        let rest = rest.trim();
+        let (status, by) = match rest.strip_suffix(')').and_then(|r| r.rsplit_once(" (by ")) {
+            Some((status, by)) => (status, Some(by.trim().to_string())),
+            None => (rest, None),
+        };
+        Some(Self {
+            date: date.trim().to_string(),
+            status: status.trim().to_string(),
+            by,
+        })
+    }
+
+    /// The entry as a `## Status History` list item, without line break.
+    pub fn to_line(&self) -> String {
+        match &self.by {
+            Some(by) => format!("- {}: {} (by {})", self.date, self.status, by),
+            None => format!("- {}: {}", self.date, self.status),
+        }
+    }
+}
+
+/// Date of the latest decision (Accepted or Rejected) in `history`. ADRs without
+/// history count `date` as the decision date when `status` is a decision.
+pub fn decision_date(status: &str, date: &str, history: &[StatusChange]) -> Option<String> {
+    if history.is_empty() {
+        return matches!(status_class(status), "accepted" | "rejected").then(|| date.to_string());
+    }
+    history
+        .iter()
+        .rev()
+        .find(|c| matches!(status_class(&c.status), "accepted" | "rejected"))
+        .map(|c| c.date.clone())
+}
+
 pub fn slugify(s: &str) -> String {
     let mut out = String::with_capacity(s.len());
     let mut last_dash = false;
@@ -47,10 +289,116 @@ pub fn parse_number(s: &str) -> anyhow::Result<u32> {
     }
 }
 
+/// Groups a free-form status into a stable class name used for styling
+/// (`proposed`, `accepted`, `rejected`, `superseded`, `deprecated` or `other`).
+pub fn status_class(status: &str) -> &'static str {
+    let s = status.trim().to_ascii_lowercase();
+    if s.starts_with("superseded") {
+        "superseded"
+    } else if s == "accepted" {
+        "accepted"
+    } else if s == "proposed" {
+        "proposed"
+    } else if s == "rejected" {
+        "rejected"
+    } else if s == "deprecated" {
+        "deprecated"
+    } else {
+        "other"
+    }
+}
+
+/// True for decisions still in force or under discussion; superseded, deprecated
+/// and rejected ADRs are inactive.
+pub fn is_active(status: &str) -> bool {
+    !matches!(
+        status_class(status),
+        "superseded" | "deprecated" | "rejected"
+    )
+}
+
+/// Matches a `list --status` filter: `active`, `inactive` or a status name,
+/// ignoring case (`superseded` also matches `Superseded by NNNN`).
+pub fn matches_status(status: &str, filter: &str) -> bool {
+    let filter = filter.trim();
+    if filter.eq_ignore_ascii_case("active") {
+        is_active(status)
+    } else if filter.eq_ignore_ascii_case("inactive") {
+        !is_active(status)
+    } else {
+        status.trim().eq_ignore_ascii_case(filter)
+            || (filter.eq_ignore_ascii_case("superseded") && status_class(status) == "superseded")
+    }
+}
+
+/// Parses an ADR reference as written in `Supersedes:`/`Superseded-by:` lines,
+/// accepting both plain numbers (`0003`) and links (`[0003](0003-foo.md)`).
+pub fn parse_adr_ref(s: &str) -> Option<u32> {
+    let s = s.trim();
+    let s = s.strip_prefix('[').unwrap_or(s);
+    let digits: String = s.chars().take_while(|c| c.is_ascii_digit()).collect();
+    if digits.is_empty() {
+        None
+    } else {
+        digits.parse::<u32>().ok()
+    }
+}
+
+/// Parses a comma-separated list of ADR references, e.g. the value of
+/// `Supersedes: [0003](0003-a.md), 0005`. Items that are not references are skipped.
+pub fn parse_adr_refs(s: &str) -> Vec<u32> {
+    s.split(',').filter_map(parse_adr_ref).collect()
+}
+
+/// Parses a comma-separated list value such as `Tags:` or `Deciders:`. Empty items
+/// and case-insensitive duplicates are dropped; the first spelling wins.
+pub fn parse_list(s: &str) -> Vec<String> {
+    let mut items: Vec<String> = Vec::new();
+    for item in s.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+        if !list_contains(&items, item) {
+            items.push(item.to_string());
+        }
+    }
+    items
+}
+
+/// True when `items` contains `item`, ignoring case.
+pub fn list_contains(items: &[String], item: &str) -> bool {
+    items.iter().any(|t| t.eq_ignore_ascii_case(item.trim()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_tags() {
+        assert_eq!(
+            parse_list(" database, Security ,,database, DATABASE"),
+            vec!["database", "Security"]
+        );
+        assert!(parse_list("").is_empty());
+        assert!(list_contains(&parse_list("database"), " Database"));
+    }
+
+    #[test]
+    fn test_people_merge_and_lookup() {
+        let current = People {
+            deciders: vec!["Ada".into()],
+            ..People::default()
+        };
+        let added = People {
+            deciders: vec!["ada".into(), " Grace ".into()],
+            informed: vec!["Ops".into()],
+            ..People::default()
+        };
+        let merged = current.merged(&added);
+        assert_eq!(merged.deciders, vec!["Ada", "Grace"]);
+        assert_eq!(merged.get(Role::Informed), ["Ops".to_string()]);
+        assert!(merged.authors.is_empty());
+        assert!(merged.contains("grace") && !merged.contains("Linus"));
+    }
+
     #[test]
     fn test_slugify() {
         assert_eq!(slugify("Hello World"), "hello-world");
@@ -71,4 +419,79 @@ mod tests {
         assert_eq!(parse_number("0000").unwrap(), 0);
         assert!(parse_number("abc").is_err());
     }
+
+    #[test]
+    fn test_parse_adr_ref() {
+        assert_eq!(parse_adr_ref("0003"), Some(3));
+        assert_eq!(parse_adr_ref(" [0012](0012-foo.md)"), Some(12));
+        assert_eq!(parse_adr_ref("none"), None);
+        assert_eq!(
+            parse_adr_refs("[0003](0003-a.md), 0005,8, none"),
+            vec![3, 5, 8]
+        );
+    }
+
+    #[test]
+    fn test_status_change_lines_round_trip() {
+        let c = StatusChange::parse_line("- 2024-02-01: Accepted (by Ada Lovelace)").unwrap();
+        assert_eq!(c.date, "2024-02-01");
+        assert_eq!(c.status, "Accepted");
+        assert_eq!(c.by.as_deref(), Some("Ada Lovelace"));
+        assert_eq!(c.to_line(), "- 2024-02-01: Accepted (by Ada Lovelace)");
+        let c = StatusChange::parse_line("- 2024-03-01: Superseded by 0004").unwrap();
+        assert_eq!(c.status, "Superseded by 0004");
+        assert_eq!(c.by, None);
+        assert!(StatusChange::parse_line("Some prose").is_none());
+    }
+
+    #[test]
+    fn test_link_kinds_are_reciprocal() {
+        for kind in LinkKind::ALL {
+            assert_eq!(kind.reciprocal().reciprocal(), kind);
+            assert_eq!(
+                kind.yaml_key(),
+                kind.header_key().to_ascii_lowercase().replace('-', "_")
+            );
+        }
+        assert_eq!(
+            LinkKind::from_str("depends-on", true).unwrap(),
+            LinkKind::DependsOn
+        );
+        assert!(LinkKind::from_str("amended-by", true).is_err());
+    }
+
+    #[test]
+    fn test_status_filters() {
+        assert!(matches_status("Accepted", "active"));
+        assert!(matches_status("Proposed", "ACTIVE"));
+        assert!(matches_status("Deprecated", "inactive"));
+        assert!(matches_status("Superseded by 0004", "inactive"));
+        assert!(!matches_status("Deprecated", "active"));
+        assert!(matches_status("Superseded by 0004", "superseded"));
+        assert!(matches_status("In Review", "in review"));
+        assert!(!matches_status("Accepted", "rejected"));
+    }
+
+    #[test]
+    fn test_decision_date() {
+        let change = |date: &str, status: &str| StatusChange {
+            date: date.into(),
+            status: status.into(),
+            by: None,
+        };
+        let history = vec![
+            change("2024-01-01", "Proposed"),
+            change("2024-02-01", "Accepted"),
+            change("2024-05-01", "Deprecated"),
+        ];
+        assert_eq!(
+            decision_date("Deprecated", "2024-05-01", &history).as_deref(),
+            Some("2024-02-01")
+        );
+        assert_eq!(decision_date("Proposed", "2024-01-01", &[]), None);
+        assert_eq!(
+            decision_date("Accepted", "2024-01-01", &[]).as_deref(),
+            Some("2024-01-01")
+        );
+    }
 }
@@ -0,0 +1,246 @@
+use clap::ValueEnum;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+use crate::domain::{status_class, AdrMeta};
+
+/// Diagram languages supported by `radr graph`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum GraphFormat {
+    #[default]
+    Mermaid,
+    Dot,
+    Plantuml,
+}
+
+/// Class name plus fill/stroke colors for a status.
+fn status_colors(status: &str) -> (&'static str, &'static str, &'static str) {
+    let class = status_class(status);
+    let (fill, stroke) = match class {
+        "superseded" => ("#e2e3e5", "#6c757d"),
+        "accepted" => ("#d4edda", "#28a745"),
+        "proposed" => ("#fff3cd", "#ffc107"),
+        "rejected" => ("#f8d7da", "#dc3545"),
+        "deprecated" => ("#ede7f6", "#6f42c1"),
+        _ => ("#ffffff", "#343a40"),
+    };
+    (class, fill, stroke)
+}
+
+fn node_id(n: u32) -> String {
+    format!("adr{:04}", n)
+}
+
+fn label(a: &AdrMeta) -> String {
+    format!("{:04}: {}", a.number, a.title)
+}
+
+/// Supersede edges as (newer, older) pairs, collected from both sides of the link.
+fn edges(adrs: &[AdrMeta]) -> BTreeSet<(u32, u32)> {
+    let mut out = BTreeSet::new();
+    for a in adrs {
+        for &old in &a.supersedes {
+            out.insert((a.number, old));
+        }
+        if let Some(new) = a.superseded_by {
+            out.insert((new, a.number));
+        }
+    }
+    out
+}
+
+/// Restricts the ADR set to the supersede chain around `focus`, following links in
+/// both directions up to `depth` hops (unlimited when `None`).
+pub fn select(adrs: &[AdrMeta], focus: u32, depth: Option<usize>) -> Vec<AdrMeta> {
+    let mut neighbours: HashMap<u32, Vec<u32>> = HashMap::new();
+    for (new, old) in edges(adrs) {
+        neighbours.entry(new).or_default().push(old);
+        neighbours.entry(old).or_default().push(new);
+    }
+    let mut seen: HashSet<u32> = HashSet::from([focus]);
+    let mut queue = VecDeque::from([(focus, 0usize)]);
+    while let Some((n, d)) = queue.pop_front() {
+        if depth.is_some_and(|max| d >= max) {
+            continue;
+        }
+        for &m in neighbours.get(&n).into_iter().flatten() {
+            if seen.insert(m) {
+                queue.push_back((m, d + 1));
+            }
+        }
+    }
+    adrs.iter()
+        .filter(|a| seen.contains(&a.number))
+        .cloned()
+        .collect()
+}
+
+/// Renders the supersede graph of `adrs` as diagram source. Edges to ADRs outside
+/// the given set are dropped.
+pub fn render(format: GraphFormat, adrs: &[AdrMeta]) -> String {
+    let present: HashSet<u32> = adrs.iter().map(|a| a.number).collect();
+    let edges: Vec<(u32, u32)> = edges(adrs)
+        .into_iter()
+        .filter(|(new, old)| present.contains(new) && present.contains(old))
+        .collect();
+    match format {
+        GraphFormat::Mermaid => render_mermaid(adrs, &edges),
+        GraphFormat::Dot => render_dot(adrs, &edges),
+        GraphFormat::Plantuml => render_plantuml(adrs, &edges),
+    }
+}
+
+fn render_mermaid(adrs: &[AdrMeta], edges: &[(u32, u32)]) -> String {
+    let mut out = String::from("flowchart TD\n");
+    for a in adrs {
+        out.push_str(&format!(
+            "    {}[\"{}\"]\n",
+            node_id(a.number),
+            label(a).replace('"', "#quot;")
+        ));
+    }
+    for (new, old) in edges {
+        out.push_str(&format!(
+            "    {} -->|supersedes| {}\n",
+            node_id(*new),
+            node_id(*old)
+        ));
+    }
+    let mut classes: Vec<(&str, &str, &str)> = Vec::new();
+    for a in adrs {
+        let c = status_colors(&a.status);
+        if !classes.contains(&c) {
+            classes.push(c);
+        }
+    }
+    for (class, fill, stroke) in &classes {
+        out.push_str(&format!(
+            "    classDef {} fill:{},stroke:{}\n",
+            class, fill, stroke
+        ));
+    }
+    for a in adrs {
+        out.push_str(&format!(
+            "    class {} {}\n",
+            node_id(a.number),
+            status_colors(&a.status).0
+        ));
+    }
+    out
+}
+
+fn render_dot(adrs: &[AdrMeta], edges: &[(u32, u32)]) -> String {
+    let mut out = String::from("digraph adrs {\n");
+    out.push_str("    rankdir=TB;\n");
+    out.push_str("    node [shape=box, style=\"rounded,filled\"];\n");
+    for a in adrs {
+        let (_, fill, stroke) = status_colors(&a.status);
+        out.push_str(&format!(
+            "    {} [label=\"{}\", fillcolor=\"{}\", color=\"{}\"];\n",
+            node_id(a.number),
+            label(a).replace('\\', "\\\\").replace('"', "\\\""),
+            fill,
+            stroke
+        ));
+    }
+    for (new, old) in edges {
+        out.push_str(&format!(
+            "    {} -> {} [label=\"supersedes\"];\n",
+            node_id(*new),
+            node_id(*old)
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn render_plantuml(adrs: &[AdrMeta], edges: &[(u32, u32)]) -> String {
+    let mut out = String::from("@startuml\n");
+    for a in adrs {
+        let (_, fill, _) = status_colors(&a.status);
+        out.push_str(&format!(
+            "rectangle \"{}\" as {} {}\n",
+            label(a).replace('"', "'"),
+            node_id(a.number),
+            fill
+        ));
+    }
+    for (new, old) in edges {
+        out.push_str(&format!(
+            "{} --> {} : supersedes\n",
+            node_id(*new),
+            node_id(*old)
+        ));
+    }
+    out.push_str("@enduml\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::People;
+    use std::path::PathBuf;
+
+    fn meta(number: u32, status: &str, supersedes: Option<u32>) -> AdrMeta {
+        AdrMeta {
+            number,
+            title: format!("Title {}", number),
+            status: status.to_string(),
+            date: "2024-01-01".to_string(),
+            created: "2024-01-01".to_string(),
+            decided: None,
+            history: Vec::new(),
+            supersedes: supersedes.into_iter().collect(),
+            superseded_by: None,
+            deprecation_reason: None,
+            links: Vec::new(),
+            tags: Vec::new(),
+            people: People::default(),
+            path: PathBuf::from(format!("{:04}-title.md", number)),
+        }
+    }
+
+    fn chain() -> Vec<AdrMeta> {
+        let mut v = vec![
+            meta(1, "Superseded by 0002", None),
+            meta(2, "Superseded by 0003", Some(1)),
+            meta(3, "Accepted", Some(2)),
+            meta(4, "Proposed", None),
+        ];
+        v[0].superseded_by = Some(2);
+        v[1].superseded_by = Some(3);
+        v
+    }
+
+    #[test]
+    fn mermaid_has_nodes_edges_and_classes() {
+        let out = render(GraphFormat::Mermaid, &chain());
+        assert!(out.starts_with("flowchart TD\n"));
+        assert!(out.contains("adr0001[\"0001: Title 1\"]"));
+        assert!(out.contains("adr0002 -->|supersedes| adr0001"));
+        assert!(out.contains("classDef superseded fill:#e2e3e5"));
+        assert!(out.contains("class adr0003 accepted"));
+        assert_eq!(out.matches("-->").count(), 2);
+    }
+
+    #[test]
+    fn dot_and_plantuml_render_edges() {
+        let dot = render(GraphFormat::Dot, &chain());
+        assert!(dot.contains("adr0003 -> adr0002 [label=\"supersedes\"];"));
+        assert!(dot.contains("fillcolor=\"#fff3cd\""));
+        let puml = render(GraphFormat::Plantuml, &chain());
+        assert!(puml.starts_with("@startuml\n") && puml.ends_with("@enduml\n"));
+        assert!(puml.contains("rectangle \"0004: Title 4\" as adr0004 #fff3cd"));
+        assert!(puml.contains("adr0002 --> adr0001 : supersedes"));
+    }
+
+    #[test]
+    fn select_limits_chain_by_depth() {
+        let adrs = chain();
+        let nums = |v: Vec<AdrMeta>| v.iter().map(|a| a.number).collect::<Vec<_>>();
+        assert_eq!(nums(select(&adrs, 1, Some(1))), vec![1, 2]);
+        assert_eq!(nums(select(&adrs, 2, Some(1))), vec![1, 2, 3]);
+        assert_eq!(nums(select(&adrs, 1, None)), vec![1, 2, 3]);
+        assert_eq!(nums(select(&adrs, 4, None)), vec![4]);
+    }
+}
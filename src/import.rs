@@ -0,0 +1,272 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::Local;
+use regex::Regex;
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+
+use crate::actions::{render_adr, write_index, Extras};
+use crate::config::Config;
+use crate::domain::{decision_date, parse_adr_ref, slugify, AdrMeta, People};
+use crate::repository::AdrRepository;
+
+/// An adr-tools record parsed from disk, before conversion.
+#[derive(Debug, Clone)]
+struct AdrToolsRecord {
+    number: u32,
+    title: String,
+    date: Option<String>,
+    status: String,
+    supersedes: Vec<u32>,
+    superseded_by: Option<u32>,
+    body: String,
+}
+
+/// Imports ADRs written by adr-tools (npryce) from `src` into the repository,
+/// converting them to the layout selected by `cfg`. Numbers, dates and supersede
+/// links are kept; importing fails if any number is already taken.
+pub fn import_adr_tools<R: AdrRepository>(
+    repo: &R,
+    cfg: &Config,
+    src: &Path,
+) -> Result<Vec<AdrMeta>> {
+    let re = Regex::new(r"^(\d{4})-.*\.md$").map_err(|e| anyhow!("invalid regex: {}", e))?;
+    let mut records = Vec::new();
+    for entry in fs::read_dir(src)
+        .with_context(|| format!("Reading adr-tools directory at {}", src.display()))?
+    {
+        let path = entry?.path();
+        let fname = path.file_name().and_then(OsStr::to_str).unwrap_or("");
+        let Some(caps) = re.captures(fname) else {
+            continue;
+        };
+        let number = caps[1].parse::<u32>()?;
+        let raw = fs::read_to_string(&path)
+            .with_context(|| format!("Reading ADR at {}", path.display()))?;
+        records.push(parse_adr_tools(number, &raw));
+    }
+    records.sort_by_key(|r| r.number);
+
+    let existing = repo.list()?;
+    for r in &records {
+        if let Some(a) = existing.iter().find(|a| a.number == r.number) {
+            return Err(anyhow!(
+                "ADR {:04} already exists at {}",
+                r.number,
+                a.path.display()
+            ));
+        }
+    }
+
+    let ext = cfg.format.as_str();
+    let mut by_number: HashMap<u32, String> = HashMap::new();
+    for a in &existing {
+        if let Some(fname) = a.path.file_name().and_then(OsStr::to_str) {
+            by_number.insert(a.number, fname.to_string());
+        }
+    }
+    for r in &records {
+        by_number.insert(
+            r.number,
+            format!("{:04}-{}.{}", r.number, slugify(&r.title), ext),
+        );
+    }
+
+    let today = Local::now().format("%Y-%m-%d").to_string();
+    let mut imported = Vec::with_capacity(records.len());
+    for r in records {
+        let path = repo.adr_dir().join(&by_number[&r.number]);
+        let status = match r.superseded_by {
+            Some(n) => format!("Superseded by {:04}", n),
+            None => r.status,
+        };
+        let date = r.date.unwrap_or_else(|| today.clone());
+        let meta = AdrMeta {
+            number: r.number,
+            title: r.title,
+            decided: decision_date(&status, &date, &[]),
+            created: date.clone(),
+            history: Vec::new(),
+            status,
+            date,
+            supersedes: r.supersedes,
+            superseded_by: r.superseded_by,
+            deprecation_reason: None,
+            links: Vec::new(),
+            tags: Vec::new(),
+            people: People::default(),
+            path,
+        };
+        let content = render_adr(cfg, &meta, &by_number, &r.body, &Extras::default());
+        repo.write_string(&meta.path, &content)?;
+        imported.push(meta);
+    }
+
+    let adrs = repo.list()?;
+    write_index(repo, cfg, &adrs)?;
+    Ok(imported)
+}
+
+/// Parses the adr-tools layout: `# N. Title`, a `Date:` line, and a `## Status`
+/// section holding the status word plus `Supersedes`/`Superseded by` links (one
+/// `Supersedes` line per replaced ADR).
+fn parse_adr_tools(number: u32, raw: &str) -> AdrToolsRecord {
+    let mut title = String::new();
+    let mut date = None;
+    let mut status = String::new();
+    let mut supersedes = Vec::new();
+    let mut superseded_by = None;
+    let mut body: Vec<&str> = Vec::new();
+    // Status section lines that are neither the status nor a supersede link
+    let mut extra_status: Vec<&str> = Vec::new();
+
+    let mut in_status = false;
+    let mut in_body = false;
+    for line in raw.lines() {
+        if in_body {
+            body.push(line);
+            continue;
+        }
+        if title.is_empty() {
+            if let Some(h) = line.strip_prefix("# ") {
+                title = match h.split_once(". ") {
+                    Some((n, t)) if n.trim().chars().all(|c| c.is_ascii_digit()) => {
+                        t.trim().to_string()
+                    }
+                    _ => h.trim().to_string(),
+                };
+                continue;
+            }
+        }
+        if let Some(d) = line.strip_prefix("Date:") {
+            date = Some(d.trim().to_string());
+            continue;
+        }
+        if let Some(heading) = line.strip_prefix("## ") {
+            if heading.trim().eq_ignore_ascii_case("status") {
+                in_status = true;
+            } else {
+                in_status = false;
+                in_body = true;
+                body.push(line);
+            }
+            continue;
+        }
+        if !in_status || line.trim().is_empty() {
+            continue;
+        }
+        let l = line.trim();
+        if let Some(rest) = l.strip_prefix("Superseded by") {
+            superseded_by = link_target(rest);
+        } else if let Some(rest) = l.strip_prefix("Supersedes") {
+            supersedes.extend(link_target(rest));
+        } else if status.is_empty() {
+            status = l.to_string();
+        } else {
+            extra_status.push(line);
+        }
+    }
+
+    if status.is_empty() {
+        status = if superseded_by.is_some() {
+            "Superseded".to_string()
+        } else {
+            "Proposed".to_string()
+        };
+    }
+    let mut text = String::new();
+    if !extra_status.is_empty() {
+        text.push_str(&extra_status.join("\n"));
+        text.push_str("\n\n");
+    }
+    text.push_str(body.join("\n").trim_end());
+    text.push('\n');
+
+    AdrToolsRecord {
+        number,
+        title,
+        date,
+        status,
+        supersedes,
+        superseded_by,
+        body: text,
+    }
+}
+
+/// Extracts the ADR number from `[3. Foo](0003-foo.md)`, preferring the filename.
+fn link_target(s: &str) -> Option<u32> {
+    let s = s.trim();
+    if let Some(start) = s.find("](") {
+        let target = &s[start + 2..];
+        if let Some(n) = parse_adr_ref(target) {
+            return Some(n);
+        }
+    }
+    parse_adr_ref(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::fs::FsAdrRepository;
+    use tempfile::tempdir;
+
+    const OLD: &str = "# 1. Use Foo\n\nDate: 2016-02-12\n\n## Status\n\nSuperseded by [2. Use Bar](0002-use-bar.md)\n\n## Context\n\nWe need foo.\n\n## Decision\n\nUse foo.\n";
+    const NEW: &str = "# 2. Use Bar\n\nDate: 2017-03-01\n\n## Status\n\nAccepted\n\nSupersedes [1. Use Foo](0001-use-foo.md)\n\nAmends [0. Zero](0000-zero.md)\n\n## Context\n\nBar is better.\n";
+
+    #[test]
+    fn parses_status_section_and_links() {
+        let r = parse_adr_tools(1, OLD);
+        assert_eq!(r.title, "Use Foo");
+        assert_eq!(r.date.as_deref(), Some("2016-02-12"));
+        assert_eq!(r.superseded_by, Some(2));
+        assert_eq!(r.status, "Superseded");
+        assert!(r.body.starts_with("## Context\n\nWe need foo."));
+
+        let r = parse_adr_tools(2, NEW);
+        assert_eq!(r.status, "Accepted");
+        assert_eq!(r.supersedes, vec![1]);
+        // Unknown status-section lines are kept at the top of the body
+        assert!(r
+            .body
+            .starts_with("Amends [0. Zero](0000-zero.md)\n\n## Context"));
+    }
+
+    #[test]
+    fn imports_into_configured_layout() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("legacy");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(src.join("0001-use-foo.md"), OLD).unwrap();
+        std::fs::write(src.join("0002-use-bar.md"), NEW).unwrap();
+        std::fs::write(src.join("README.md"), "ignored").unwrap();
+
+        let adr_dir = dir.path().join("adrs");
+        let repo = FsAdrRepository::new(&adr_dir);
+        let cfg = Config {
+            adr_dir: adr_dir.clone(),
+            format: "mdx".into(),
+            front_matter: true,
+            ..Config::default()
+        };
+        let imported = import_adr_tools(&repo, &cfg, &src).unwrap();
+        assert_eq!(imported.len(), 2);
+
+        let adrs = repo.list().unwrap();
+        assert_eq!(adrs[0].status, "Superseded by 0002");
+        assert_eq!(adrs[0].date, "2016-02-12");
+        assert_eq!(adrs[0].superseded_by, Some(2));
+        assert_eq!(adrs[1].status, "Accepted");
+        assert_eq!(adrs[1].supersedes, vec![1]);
+
+        let c = std::fs::read_to_string(adr_dir.join("0002-use-bar.mdx")).unwrap();
+        assert!(c.starts_with("---\ntitle: Use Bar\n---\n"));
+        assert!(c.contains("Supersedes: [0001](0001-use-foo.mdx)"));
+        assert!(c.contains("## Context\n\nBar is better.\n"));
+
+        // Re-importing would clash with existing numbers
+        let err = import_adr_tools(&repo, &cfg, &src).unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+    }
+}
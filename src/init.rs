@@ -0,0 +1,189 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::Local;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::actions::{render_adr, write_index, Extras};
+use crate::config::{render_config, Config, ConfigFormat, CONFIG_FILES};
+use crate::domain::{decision_date, slugify, AdrMeta, People};
+use crate::repository::{idx_path, AdrRepository};
+
+const FIRST_ADR_TITLE: &str = "Record architecture decisions";
+
+const FIRST_ADR_BODY: &str = "## Context\n\nWe need to record the architectural decisions made on this project.\n\n## Decision\n\nWe will use Architecture Decision Records, as described by Michael Nygard in [Documenting Architecture Decisions](https://cognitect.com/blog/2011/11/15/documenting-architecture-decisions).\n\n## Consequences\n\nSee Michael Nygard's article, linked above. The records live next to the code and are managed with radr (`radr new`, `radr list`, `radr lint`).\n";
+
+/// Files and directories written by `radr init`.
+#[derive(Debug, Clone, Serialize)]
+pub struct InitReport {
+    pub config: PathBuf,
+    pub adr_dir: PathBuf,
+    pub index: PathBuf,
+    pub first_adr: Option<AdrMeta>,
+}
+
+/// Sets up a repository for `cfg`: writes the config file at `config_path`,
+/// creates the ADR directory and its index and, when `first_adr` is given, adds
+/// the accepted "Record architecture decisions" ADR with those people. Refuses to
+/// touch an existing config or ADR directory unless `force` is set; the first ADR
+/// is only added to an empty directory. The config names `cfg.adr_dir` relative
+/// to its own directory when it lies below it.
+pub fn init<R: AdrRepository>(
+    repo: &R,
+    cfg: &Config,
+    config_path: &Path,
+    config_format: ConfigFormat,
+    first_adr: Option<&People>,
+    force: bool,
+) -> Result<InitReport> {
+    let adrs = repo.list()?;
+    let index = idx_path(&cfg.adr_dir, &cfg.index_name);
+    let config_dir = config_path.parent().unwrap_or(Path::new(""));
+    if !force {
+        // Any config discoverable next to the new one counts as an existing setup
+        let existing = std::iter::once(config_path.to_path_buf())
+            .chain(CONFIG_FILES.iter().map(|f| config_dir.join(f)))
+            .chain(std::iter::once(index.clone()))
+            .find(|p| p.exists());
+        if let Some(p) = existing {
+            return Err(anyhow!(
+                "radr is already set up here ({} exists); use --force to overwrite",
+                p.display()
+            ));
+        }
+        if !adrs.is_empty() {
+            return Err(anyhow!(
+                "{} already holds {} ADR(s); use --force to set up radr anyway",
+                cfg.adr_dir.display(),
+                adrs.len()
+            ));
+        }
+    }
+
+    // The config names the ADR directory relative to itself
+    let written = Config {
+        adr_dir: cfg
+            .adr_dir
+            .strip_prefix(config_dir)
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|_| cfg.adr_dir.clone()),
+        ..cfg.clone()
+    };
+    fs::write(config_path, render_config(&written, config_format))
+        .with_context(|| format!("Writing config at {}", config_path.display()))?;
+    fs::create_dir_all(&cfg.adr_dir)
+        .with_context(|| format!("Creating ADR directory at {}", cfg.adr_dir.display()))?;
+
+    let first_adr = match first_adr {
+        Some(people) if adrs.is_empty() => {
+            let date = Local::now().format("%Y-%m-%d").to_string();
+            let status = "Accepted".to_string();
+            let filename = format!("{:04}-{}.{}", 1, slugify(FIRST_ADR_TITLE), cfg.format);
+            let meta = AdrMeta {
+                number: 1,
+                title: FIRST_ADR_TITLE.to_string(),
+                decided: decision_date(&status, &date, &[]),
+                created: date.clone(),
+                history: Vec::new(),
+                status,
+                date,
+                supersedes: Vec::new(),
+                superseded_by: None,
+                deprecation_reason: None,
+                links: Vec::new(),
+                tags: Vec::new(),
+                people: people.clone(),
+                path: repo.adr_dir().join(filename),
+            };
+            let content = render_adr(
+                cfg,
+                &meta,
+                &HashMap::new(),
+                FIRST_ADR_BODY,
+                &Extras::default(),
+            );
+            repo.write_string(&meta.path, &content)?;
+            Some(meta)
+        }
+        _ => None,
+    };
+    write_index(repo, cfg, &repo.list()?)?;
+
+    Ok(InitReport {
+        config: config_path.to_path_buf(),
+        adr_dir: cfg.adr_dir.clone(),
+        index,
+        first_adr,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::layered_config;
+    use crate::repository::fs::FsAdrRepository;
+    use tempfile::tempdir;
+
+    #[test]
+    fn sets_up_config_directory_index_and_first_adr() {
+        let dir = tempdir().unwrap();
+        let adr_dir = dir.path().join("adrs");
+        let config_path = dir.path().join("radr.yaml");
+        let repo = FsAdrRepository::new(&adr_dir);
+        let cfg = Config {
+            adr_dir: adr_dir.clone(),
+            format: "mdx".into(),
+            front_matter: true,
+            ..Config::default()
+        };
+        let people = People {
+            authors: vec!["Ada".into()],
+            ..People::default()
+        };
+        let report = init(
+            &repo,
+            &cfg,
+            &config_path,
+            ConfigFormat::Yaml,
+            Some(&people),
+            false,
+        )
+        .unwrap();
+
+        assert!(std::fs::read_to_string(&config_path)
+            .unwrap()
+            .contains("\nadr_dir: \"adrs\"\n"));
+        let loaded = layered_config(None, Some(&config_path), |_| None, &[])
+            .unwrap()
+            .config;
+        assert_eq!(loaded.adr_dir, adr_dir);
+        assert_eq!(loaded.format, "mdx");
+        let first = report.first_adr.unwrap();
+        assert_eq!(
+            first.path,
+            adr_dir.join("0001-record-architecture-decisions.mdx")
+        );
+        let adrs = repo.list().unwrap();
+        assert_eq!(adrs.len(), 1);
+        assert_eq!(adrs[0].status, "Accepted");
+        assert_eq!(adrs[0].people.authors, vec!["Ada".to_string()]);
+        let index = std::fs::read_to_string(adr_dir.join("index.md")).unwrap();
+        assert!(index.contains("Record architecture decisions"));
+
+        let err = init(&repo, &cfg, &config_path, ConfigFormat::Yaml, None, false).unwrap_err();
+        assert!(err.to_string().contains("already set up"));
+        // Forcing rewrites the config but leaves existing ADRs alone
+        let report = init(
+            &repo,
+            &cfg,
+            &config_path,
+            ConfigFormat::Yaml,
+            Some(&people),
+            true,
+        )
+        .unwrap();
+        assert!(report.first_adr.is_none());
+        assert_eq!(repo.list().unwrap().len(), 1);
+    }
+}
@@ -1,9 +1,18 @@
 pub mod actions;
 pub mod config;
+pub mod document;
 pub mod domain;
+pub mod graph;
+pub mod import;
+pub mod init;
+pub mod lint;
+pub mod output;
 pub mod repository;
+pub mod show;
+pub mod site;
 pub mod yaml_util;
 
 pub use crate::config::Config;
+pub use crate::document::AdrDocument;
 pub use crate::domain::{parse_number, AdrMeta};
 pub use crate::repository::fs::FsAdrRepository;
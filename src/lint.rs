@@ -0,0 +1,1016 @@
+use anyhow::Result;
+use chrono::{Local, NaiveDate};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsStr;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::actions::{read_tag_indexes, render_index, render_tag_indexes, write_index};
+use crate::config::Config;
+use crate::document::AdrDocument;
+use crate::domain::{is_active, parse_adr_ref, slugify, status_class, AdrMeta, LinkKind};
+use crate::repository::{idx_path, AdrRepository};
+use crate::yaml_util::has_yaml_field;
+use serde_yaml::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A single problem reported by a lint rule.
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub number: Option<u32>,
+    pub path: Option<PathBuf>,
+    pub message: String,
+}
+
+/// Everything a rule can inspect: parsed metadata plus the raw file contents,
+/// so rules can tell apart values that were present from parser defaults.
+pub struct LintContext<'a> {
+    pub adrs: &'a [AdrMeta],
+    pub contents: &'a [String],
+    pub today: NaiveDate,
+    pub cfg: &'a Config,
+    /// Current index file contents, if it exists.
+    pub index: Option<&'a str>,
+    /// Per-tag index files currently next to the index, as names and contents.
+    pub tag_indexes: &'a [(String, String)],
+}
+
+/// A hit produced by a rule check; `adr` indexes into `LintContext::adrs`.
+pub struct Hit {
+    pub adr: Option<usize>,
+    pub message: String,
+}
+
+pub struct Rule {
+    pub id: &'static str,
+    pub severity: Severity,
+    pub description: &'static str,
+    check: fn(&LintContext) -> Vec<Hit>,
+}
+
+pub const RULES: &[Rule] = &[
+    Rule {
+        id: "duplicate-number",
+        severity: Severity::Error,
+        description: "Two or more ADR files share the same number",
+        check: check_duplicate_number,
+    },
+    Rule {
+        id: "dangling-link",
+        severity: Severity::Error,
+        description:
+            "Supersedes/Superseded-by or a typed link points to an ADR that does not exist",
+        check: check_dangling_link,
+    },
+    Rule {
+        id: "malformed-field",
+        severity: Severity::Error,
+        description: "Front matter is not valid YAML, or a link field holds something other than ADR references",
+        check: check_malformed_field,
+    },
+    Rule {
+        id: "asymmetric-supersede",
+        severity: Severity::Warning,
+        description: "Supersedes and Superseded-by do not point at each other",
+        check: check_asymmetric_supersede,
+    },
+    Rule {
+        id: "supersede-cycle",
+        severity: Severity::Error,
+        description: "The supersede chain loops back on itself",
+        check: check_supersede_cycle,
+    },
+    Rule {
+        id: "title-mismatch",
+        severity: Severity::Warning,
+        description: "Filename slug does not match the ADR title",
+        check: check_title_mismatch,
+    },
+    Rule {
+        id: "missing-status",
+        severity: Severity::Error,
+        description: "ADR has no Status line or front matter field",
+        check: check_missing_status,
+    },
+    Rule {
+        id: "missing-date",
+        severity: Severity::Error,
+        description: "ADR has no Date line or front matter field",
+        check: check_missing_date,
+    },
+    Rule {
+        id: "invalid-date",
+        severity: Severity::Error,
+        description: "Date is not a valid YYYY-MM-DD date",
+        check: check_invalid_date,
+    },
+    Rule {
+        id: "future-date",
+        severity: Severity::Warning,
+        description: "Date lies in the future",
+        check: check_future_date,
+    },
+    Rule {
+        id: "unknown-status",
+        severity: Severity::Error,
+        description: "Status is not listed in the configured lifecycle",
+        check: check_unknown_status,
+    },
+    Rule {
+        id: "deprecation-reason",
+        severity: Severity::Warning,
+        description: "Deprecated ADR without reason, or an active ADR with one",
+        check: check_deprecation_reason,
+    },
+    Rule {
+        id: "unlinked-supersedes",
+        severity: Severity::Warning,
+        description: "Supersedes names an existing ADR without linking to its file",
+        check: check_unlinked_supersedes,
+    },
+    Rule {
+        id: "stale-index",
+        severity: Severity::Warning,
+        description: "The index file or a per-tag index is missing or out of date",
+        check: check_stale_index,
+    },
+];
+
+/// A file rewritten by `fix`, with a short description of each change.
+#[derive(Debug, Clone, Serialize)]
+pub struct FixReport {
+    pub path: PathBuf,
+    pub changes: Vec<String>,
+}
+
+/// Runs every rule against the ADRs in the repository.
+pub fn lint<R: AdrRepository>(repo: &R, cfg: &Config) -> Result<Vec<Finding>> {
+    let adrs = repo.list()?;
+    let contents = adrs
+        .iter()
+        .map(|a| repo.read_string(&a.path))
+        .collect::<Result<Vec<_>>>()?;
+    let index = repo
+        .read_string(&idx_path(&cfg.adr_dir, &cfg.index_name))
+        .ok();
+    let tag_indexes = read_tag_indexes(repo, cfg)?;
+    let ctx = LintContext {
+        adrs: &adrs,
+        contents: &contents,
+        today: Local::now().date_naive(),
+        cfg,
+        index: index.as_deref(),
+        tag_indexes: &tag_indexes,
+    };
+    Ok(run_rules(&ctx))
+}
+
+/// Runs every rule and keeps the findings about the ADR stored at `path`, e.g. to
+/// check a file right after it was edited.
+pub fn lint_file<R: AdrRepository>(repo: &R, cfg: &Config, path: &Path) -> Result<Vec<Finding>> {
+    Ok(lint(repo, cfg)?
+        .into_iter()
+        .filter(|f| f.path.as_deref() == Some(path))
+        .collect())
+}
+
+/// Repairs what can be fixed without guessing: missing reciprocal `Superseded-by`
+/// lines, unlinked `Supersedes` references, missing `Date`/`Status` lines and a
+/// stale index or per-tag index. A missing `Status` becomes the last status in the
+/// status history, else the first lifecycle status; a missing `Date` the date of
+/// the last status change, else the day the file was last modified. Fields with
+/// no such source are left for lint to report.
+pub fn fix<R: AdrRepository>(repo: &R, cfg: &Config) -> Result<Vec<FixReport>> {
+    let adrs = repo.list()?;
+    let mut reports = Vec::new();
+    for a in &adrs {
+        let raw = repo.read_string(&a.path)?;
+        let mut fields: Vec<(&str, String)> = Vec::new();
+        let mut changes = Vec::new();
+
+        if !declares_field(&raw, "Date") {
+            let date = a.history.last().map(|c| c.date.clone()).or_else(|| {
+                repo.modified(&a.path)
+                    .map(|d| d.format("%Y-%m-%d").to_string())
+            });
+            if let Some(date) = date {
+                changes.push(format!("added Date: {}", date));
+                fields.push(("Date", date));
+            }
+        }
+        let superseders: Vec<&AdrMeta> = adrs
+            .iter()
+            .filter(|o| o.supersedes.contains(&a.number))
+            .collect();
+        if a.superseded_by.is_none() && superseders.len() == 1 {
+            let n = superseders[0].number;
+            fields.push(("Status", format!("Superseded by {:04}", n)));
+            fields.push(("Superseded-by", format!("{:04}", n)));
+            changes.push(format!("added Superseded-by: {:04}", n));
+        } else if !declares_field(&raw, "Status") {
+            let status = a
+                .history
+                .last()
+                .map(|c| c.status.clone())
+                .or_else(|| cfg.lifecycle.statuses.first().cloned());
+            if let Some(status) = status {
+                changes.push(format!("added Status: {}", status));
+                fields.push(("Status", status));
+            }
+        }
+        let unlinked = unlinked_supersedes(&raw, &adrs);
+        if !unlinked.is_empty() {
+            let doc = AdrDocument::parse(&raw);
+            let items: Vec<String> = doc
+                .field("Supersedes")
+                .unwrap_or_default()
+                .split(',')
+                .map(|item| {
+                    let item = item.trim();
+                    match unlinked
+                        .iter()
+                        .find(|t| parse_adr_ref(item) == Some(t.number))
+                    {
+                        Some(t) if !item.starts_with('[') => {
+                            let link = format!("[{:04}]({})", t.number, file_name(t));
+                            changes.push(format!("linked Supersedes: {}", link));
+                            link
+                        }
+                        _ => item.to_string(),
+                    }
+                })
+                .collect();
+            fields.push(("Supersedes", items.join(", ")));
+        }
+
+        if fields.is_empty() {
+            continue;
+        }
+        let mut doc = AdrDocument::parse(&raw);
+        doc.set_fields(&fields);
+        let updated = doc.render();
+        if updated != raw {
+            repo.write_string(&a.path, &updated)?;
+            reports.push(FixReport {
+                path: a.path.clone(),
+                changes,
+            });
+        }
+    }
+
+    let adrs = repo.list()?;
+    let idx = idx_path(&cfg.adr_dir, &cfg.index_name);
+    let index = repo.read_string(&idx).ok();
+    let tag_indexes = read_tag_indexes(repo, cfg)?;
+    if !index_problems(cfg, &adrs, index.as_deref(), &tag_indexes).is_empty() {
+        write_index(repo, cfg, &adrs)?;
+        reports.push(FixReport {
+            path: idx,
+            changes: vec!["regenerated index".to_string()],
+        });
+    }
+    Ok(reports)
+}
+
+pub fn run_rules(ctx: &LintContext) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for rule in RULES {
+        for hit in (rule.check)(ctx) {
+            let adr = hit.adr.map(|i| &ctx.adrs[i]);
+            findings.push(Finding {
+                rule: rule.id,
+                severity: rule.severity,
+                number: adr.map(|a| a.number),
+                path: adr.map(|a| a.path.clone()),
+                message: hit.message,
+            });
+        }
+    }
+    findings.sort_by_key(|f| f.number);
+    findings
+}
+
+fn hit(adr: usize, message: String) -> Hit {
+    Hit {
+        adr: Some(adr),
+        message,
+    }
+}
+
+fn file_name(a: &AdrMeta) -> &str {
+    a.path.file_name().and_then(OsStr::to_str).unwrap_or("")
+}
+
+/// True when the raw ADR declares `key`, either as a front matter field
+/// (`status:`) or as a line of its metadata block (`Status:`).
+fn declares_field(raw: &str, key: &str) -> bool {
+    let doc = AdrDocument::parse(raw);
+    doc.front_matter()
+        .is_some_and(|fm| has_yaml_field(fm, &key.to_ascii_lowercase()))
+        || doc.field(key).is_some()
+}
+
+/// Returns the existing ADRs named by plain-number items of the `Supersedes:` line.
+fn unlinked_supersedes<'a>(raw: &str, adrs: &'a [AdrMeta]) -> Vec<&'a AdrMeta> {
+    let doc = AdrDocument::parse(raw);
+    let Some(value) = doc.field("Supersedes") else {
+        return Vec::new();
+    };
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.starts_with('['))
+        .filter_map(parse_adr_ref)
+        .filter_map(|n| adrs.iter().find(|a| a.number == n))
+        .collect()
+}
+
+fn check_duplicate_number(ctx: &LintContext) -> Vec<Hit> {
+    let mut by_number: HashMap<u32, Vec<usize>> = HashMap::new();
+    for (i, a) in ctx.adrs.iter().enumerate() {
+        by_number.entry(a.number).or_default().push(i);
+    }
+    let mut hits = Vec::new();
+    for (i, a) in ctx.adrs.iter().enumerate() {
+        let same = &by_number[&a.number];
+        if same.len() > 1 {
+            let others: Vec<&str> = same
+                .iter()
+                .filter(|&&j| j != i)
+                .map(|&j| file_name(&ctx.adrs[j]))
+                .collect();
+            hits.push(hit(
+                i,
+                format!(
+                    "ADR number {:04} is also used by {}",
+                    a.number,
+                    others.join(", ")
+                ),
+            ));
+        }
+    }
+    hits
+}
+
+fn check_dangling_link(ctx: &LintContext) -> Vec<Hit> {
+    let numbers: HashSet<u32> = ctx.adrs.iter().map(|a| a.number).collect();
+    let mut hits = Vec::new();
+    for (i, a) in ctx.adrs.iter().enumerate() {
+        for n in &a.supersedes {
+            if !numbers.contains(n) {
+                hits.push(hit(i, format!("Supersedes {:04}, which does not exist", n)));
+            }
+        }
+        if let Some(n) = a.superseded_by {
+            if !numbers.contains(&n) {
+                hits.push(hit(
+                    i,
+                    format!("Superseded by {:04}, which does not exist", n),
+                ));
+            }
+        }
+        for l in a.links.iter().filter(|l| !numbers.contains(&l.target)) {
+            hits.push(hit(
+                i,
+                format!(
+                    "{} {:04}, which does not exist",
+                    l.kind.header_key(),
+                    l.target
+                ),
+            ));
+        }
+    }
+    hits
+}
+
+fn check_malformed_field(ctx: &LintContext) -> Vec<Hit> {
+    let is_number = |v: &Value| v.as_u64().is_some();
+    let mut hits = Vec::new();
+    for (i, raw) in ctx.contents.iter().enumerate() {
+        let doc = AdrDocument::parse(raw);
+        match doc.front_matter_yaml() {
+            Some(Err(e)) => hits.push(hit(i, format!("Front matter is not valid YAML: {}", e))),
+            Some(Ok(Value::Mapping(map))) => {
+                let keys = ["supersedes", "superseded_by"]
+                    .into_iter()
+                    .chain(LinkKind::ALL.iter().map(|k| k.yaml_key()));
+                for key in keys {
+                    let valid = match map.get(key) {
+                        None | Some(Value::Null) => true,
+                        Some(Value::Sequence(items)) => items.iter().all(is_number),
+                        Some(v) => is_number(v),
+                    };
+                    if !valid {
+                        hits.push(hit(
+                            i,
+                            format!(
+                                "Front matter '{}' is not an ADR number or list of numbers",
+                                key
+                            ),
+                        ));
+                    }
+                }
+            }
+            Some(Ok(Value::Null)) | None => {}
+            Some(Ok(_)) => hits.push(hit(i, "Front matter is not a mapping".to_string())),
+        }
+        let keys = ["Supersedes", "Superseded-by"]
+            .into_iter()
+            .chain(LinkKind::ALL.iter().map(|k| k.header_key()));
+        for key in keys {
+            let Some(value) = doc.field(key) else {
+                continue;
+            };
+            for item in value.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+                if parse_adr_ref(item).is_none() {
+                    hits.push(hit(
+                        i,
+                        format!("{} item '{}' is not an ADR reference", key, item),
+                    ));
+                }
+            }
+        }
+    }
+    hits
+}
+
+fn check_asymmetric_supersede(ctx: &LintContext) -> Vec<Hit> {
+    let by_number: HashMap<u32, &AdrMeta> = ctx.adrs.iter().map(|a| (a.number, a)).collect();
+    let mut hits = Vec::new();
+    for (i, a) in ctx.adrs.iter().enumerate() {
+        for old in a.supersedes.iter().filter_map(|n| by_number.get(n)) {
+            if old.superseded_by != Some(a.number) {
+                hits.push(hit(
+                    i,
+                    format!(
+                        "Supersedes {:04}, but {:04} is not marked as superseded by {:04}",
+                        old.number, old.number, a.number
+                    ),
+                ));
+            }
+        }
+        if let Some(new) = a.superseded_by.and_then(|n| by_number.get(&n)) {
+            if !new.supersedes.contains(&a.number) {
+                hits.push(hit(
+                    i,
+                    format!(
+                        "Superseded by {:04}, but {:04} does not list {:04} in Supersedes",
+                        new.number, new.number, a.number
+                    ),
+                ));
+            }
+        }
+    }
+    hits
+}
+
+fn check_supersede_cycle(ctx: &LintContext) -> Vec<Hit> {
+    // Edges point from the newer ADR to the one it replaces, from either side of the link.
+    let mut edges: HashMap<u32, HashSet<u32>> = HashMap::new();
+    for a in ctx.adrs {
+        for &old in &a.supersedes {
+            edges.entry(a.number).or_default().insert(old);
+        }
+        if let Some(new) = a.superseded_by {
+            edges.entry(new).or_default().insert(a.number);
+        }
+    }
+    let index: HashMap<u32, usize> = ctx
+        .adrs
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(i, a)| (a.number, i))
+        .collect();
+
+    let mut reported: HashSet<u32> = HashSet::new();
+    let mut hits = Vec::new();
+    let mut starts: Vec<u32> = edges.keys().copied().collect();
+    starts.sort_unstable();
+    for start in starts {
+        if reported.contains(&start) {
+            continue;
+        }
+        // Depth-first search for a path leading back to `start`.
+        let mut stack: Vec<(u32, Vec<u32>)> = vec![(start, vec![start])];
+        let mut seen: HashSet<u32> = HashSet::new();
+        while let Some((node, path)) = stack.pop() {
+            let mut next: Vec<u32> = edges
+                .get(&node)
+                .map(|s| s.iter().copied().collect())
+                .unwrap_or_default();
+            next.sort_unstable();
+            for n in next {
+                if n == start {
+                    let chain = path
+                        .iter()
+                        .chain(std::iter::once(&start))
+                        .map(|n| format!("{:04}", n))
+                        .collect::<Vec<_>>()
+                        .join(" -> ");
+                    if let Some(&i) = index.get(&start) {
+                        hits.push(hit(i, format!("Supersede cycle: {}", chain)));
+                    }
+                    reported.extend(path.iter().copied());
+                    stack.clear();
+                    break;
+                }
+                if seen.insert(n) {
+                    let mut p = path.clone();
+                    p.push(n);
+                    stack.push((n, p));
+                }
+            }
+        }
+    }
+    hits
+}
+
+fn check_title_mismatch(ctx: &LintContext) -> Vec<Hit> {
+    let mut hits = Vec::new();
+    for (i, a) in ctx.adrs.iter().enumerate() {
+        let stem = a.path.file_stem().and_then(OsStr::to_str).unwrap_or("");
+        let file_slug = stem.split_once('-').map(|(_, s)| s).unwrap_or("");
+        let title_slug = slugify(&a.title);
+        if file_slug != title_slug {
+            hits.push(hit(
+                i,
+                format!(
+                    "Filename slug '{}' does not match title '{}' (expected '{}')",
+                    file_slug, a.title, title_slug
+                ),
+            ));
+        }
+    }
+    hits
+}
+
+fn check_missing_status(ctx: &LintContext) -> Vec<Hit> {
+    let mut hits = Vec::new();
+    for (i, raw) in ctx.contents.iter().enumerate() {
+        if !declares_field(raw, "Status") {
+            hits.push(hit(
+                i,
+                format!("No status found; assuming '{}'", ctx.adrs[i].status),
+            ));
+        }
+    }
+    hits
+}
+
+fn check_missing_date(ctx: &LintContext) -> Vec<Hit> {
+    let mut hits = Vec::new();
+    for (i, raw) in ctx.contents.iter().enumerate() {
+        if !declares_field(raw, "Date") {
+            hits.push(hit(
+                i,
+                format!("No date found; assuming '{}'", ctx.adrs[i].date),
+            ));
+        }
+    }
+    hits
+}
+
+fn check_invalid_date(ctx: &LintContext) -> Vec<Hit> {
+    let mut hits = Vec::new();
+    for (i, a) in ctx.adrs.iter().enumerate() {
+        if NaiveDate::parse_from_str(&a.date, "%Y-%m-%d").is_err() {
+            hits.push(hit(
+                i,
+                format!("Date '{}' is not a valid YYYY-MM-DD date", a.date),
+            ));
+        }
+    }
+    hits
+}
+
+fn check_future_date(ctx: &LintContext) -> Vec<Hit> {
+    let mut hits = Vec::new();
+    for (i, a) in ctx.adrs.iter().enumerate() {
+        if let Ok(d) = NaiveDate::parse_from_str(&a.date, "%Y-%m-%d") {
+            if d > ctx.today {
+                hits.push(hit(i, format!("Date {} lies in the future", a.date)));
+            }
+        }
+    }
+    hits
+}
+
+fn check_unknown_status(ctx: &LintContext) -> Vec<Hit> {
+    let mut hits = Vec::new();
+    for (i, a) in ctx.adrs.iter().enumerate() {
+        let s = a.status.trim();
+        // `Superseded by NNNN` is matched by prefix
+        let known = ctx.cfg.lifecycle.resolve(s).is_some()
+            || s.to_ascii_lowercase().starts_with("superseded");
+        if !known {
+            hits.push(hit(
+                i,
+                format!(
+                    "Status '{}' is not one of {}, Superseded by NNNN",
+                    a.status,
+                    ctx.cfg.lifecycle.statuses.join(", ")
+                ),
+            ));
+        }
+    }
+    hits
+}
+
+fn check_deprecation_reason(ctx: &LintContext) -> Vec<Hit> {
+    let mut hits = Vec::new();
+    for (i, a) in ctx.adrs.iter().enumerate() {
+        let deprecated = status_class(&a.status) == "deprecated";
+        if deprecated && a.deprecation_reason.is_none() {
+            hits.push(hit(
+                i,
+                "Deprecated without a Deprecation-reason; use `radr deprecate --reason`"
+                    .to_string(),
+            ));
+        } else if a.deprecation_reason.is_some() && is_active(&a.status) {
+            hits.push(hit(
+                i,
+                format!(
+                    "Has a Deprecation-reason but is still active ({})",
+                    a.status
+                ),
+            ));
+        }
+    }
+    hits
+}
+
+fn check_unlinked_supersedes(ctx: &LintContext) -> Vec<Hit> {
+    let mut hits = Vec::new();
+    for (i, raw) in ctx.contents.iter().enumerate() {
+        for target in unlinked_supersedes(raw, ctx.adrs) {
+            hits.push(hit(
+                i,
+                format!(
+                    "Supersedes {:04} is not linked to {}",
+                    target.number,
+                    file_name(target)
+                ),
+            ));
+        }
+    }
+    hits
+}
+
+fn check_stale_index(ctx: &LintContext) -> Vec<Hit> {
+    index_problems(ctx.cfg, ctx.adrs, ctx.index, ctx.tag_indexes)
+        .into_iter()
+        .map(|message| Hit { adr: None, message })
+        .collect()
+}
+
+/// How the index and the per-tag indexes on disk differ from what
+/// `write_index` would write for `adrs`.
+fn index_problems(
+    cfg: &Config,
+    adrs: &[AdrMeta],
+    index: Option<&str>,
+    tag_indexes: &[(String, String)],
+) -> Vec<String> {
+    let mut problems = Vec::new();
+    match index {
+        None => problems.push(format!("{} is missing", cfg.index_name)),
+        Some(current) if current != render_index(cfg, adrs) => {
+            problems.push(format!("{} is out of date", cfg.index_name))
+        }
+        Some(_) => {}
+    }
+    let expected = render_tag_indexes(cfg, adrs);
+    for (name, content) in &expected {
+        match tag_indexes.iter().find(|(n, _)| n == name) {
+            None => problems.push(format!("{} is missing", name)),
+            Some((_, current)) if current != content => {
+                problems.push(format!("{} is out of date", name))
+            }
+            Some(_) => {}
+        }
+    }
+    for (name, _) in tag_indexes {
+        if !expected.iter().any(|(n, _)| n == name) {
+            problems.push(format!("{} is no longer needed", name));
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{AdrLink, LinkKind, People};
+
+    fn meta(number: u32, slug: &str, title: &str) -> AdrMeta {
+        AdrMeta {
+            number,
+            title: title.to_string(),
+            status: "Accepted".to_string(),
+            date: "2024-01-01".to_string(),
+            created: "2024-01-01".to_string(),
+            decided: Some("2024-01-01".to_string()),
+            history: Vec::new(),
+            supersedes: Vec::new(),
+            superseded_by: None,
+            deprecation_reason: None,
+            links: Vec::new(),
+            tags: Vec::new(),
+            people: People::default(),
+            path: PathBuf::from(format!("{:04}-{}.md", number, slug)),
+        }
+    }
+
+    fn run(adrs: &[AdrMeta]) -> Vec<Finding> {
+        let contents: Vec<String> = adrs
+            .iter()
+            .map(|a| format!("Date: {}\nStatus: {}\n", a.date, a.status))
+            .collect();
+        let cfg = Config::default();
+        let index = render_index(&cfg, adrs);
+        let ctx = LintContext {
+            adrs,
+            contents: &contents,
+            today: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            cfg: &cfg,
+            index: Some(&index),
+            tag_indexes: &[],
+        };
+        run_rules(&ctx)
+    }
+
+    fn rules(findings: &[Finding]) -> Vec<&'static str> {
+        findings.iter().map(|f| f.rule).collect()
+    }
+
+    #[test]
+    fn clean_set_has_no_findings() {
+        let mut a = meta(1, "a", "A");
+        let mut b = meta(2, "b", "B");
+        a.superseded_by = Some(2);
+        a.status = "Superseded by 0002".into();
+        b.supersedes = vec![1];
+        assert!(run(&[a, b]).is_empty());
+    }
+
+    #[test]
+    fn reports_duplicates_and_dangling_links() {
+        let a = meta(3, "a", "A");
+        let mut b = meta(3, "b", "B");
+        b.supersedes = vec![9];
+        b.links = vec![AdrLink {
+            kind: LinkKind::DependsOn,
+            target: 7,
+        }];
+        let f = run(&[a, b]);
+        assert_eq!(
+            rules(&f),
+            vec![
+                "duplicate-number",
+                "duplicate-number",
+                "dangling-link",
+                "dangling-link"
+            ]
+        );
+        assert!(f[0].message.contains("0003-b.md"));
+        assert!(f[3]
+            .message
+            .contains("Depends-on 0007, which does not exist"));
+    }
+
+    #[test]
+    fn reports_asymmetric_pairs_and_cycles() {
+        let mut a = meta(1, "a", "A");
+        let mut b = meta(2, "b", "B");
+        a.supersedes = vec![2];
+        b.supersedes = vec![1];
+        let f = run(&[a, b]);
+        let r = rules(&f);
+        assert!(r.contains(&"asymmetric-supersede"));
+        let cycle: Vec<_> = f.iter().filter(|f| f.rule == "supersede-cycle").collect();
+        assert_eq!(cycle.len(), 1);
+        assert!(cycle[0].message.contains("0001 -> 0002 -> 0001"));
+    }
+
+    #[test]
+    fn reports_title_date_and_status_problems() {
+        let mut a = meta(1, "old-name", "New Name");
+        a.date = "2024-13-40".into();
+        let mut b = meta(2, "b", "B");
+        b.date = "2099-01-01".into();
+        b.status = "Maybe".into();
+        let r = run(&[a, b]);
+        let r = rules(&r);
+        assert!(r.contains(&"title-mismatch"));
+        assert!(r.contains(&"invalid-date"));
+        assert!(r.contains(&"future-date"));
+        assert!(r.contains(&"unknown-status"));
+    }
+
+    #[test]
+    fn reports_deprecation_reason_mismatches() {
+        let mut a = meta(1, "a", "A");
+        a.status = "Deprecated".into();
+        let mut b = meta(2, "b", "B");
+        b.deprecation_reason = Some("Obsolete".into());
+        let mut c = meta(3, "c", "C");
+        c.status = "Deprecated".into();
+        c.deprecation_reason = Some("Obsolete".into());
+        let f = run(&[a, b, c]);
+        assert_eq!(rules(&f), vec!["deprecation-reason", "deprecation-reason"]);
+        assert!(f[0].message.contains("without a Deprecation-reason"));
+        assert!(f[1].message.contains("still active (Accepted)"));
+    }
+
+    #[test]
+    fn fix_adds_reciprocal_links_missing_fields_and_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let adr_dir = dir.path().join("adrs");
+        std::fs::create_dir_all(&adr_dir).unwrap();
+        std::fs::write(adr_dir.join("0001-old.md"), "# ADR 0001: Old\n\nContext\n").unwrap();
+        std::fs::write(
+            adr_dir.join("0002-new.md"),
+            "# ADR 0002: New\n\nDate: 2024-02-01\nStatus: Proposed\nSupersedes: 0001\n\nBody\n",
+        )
+        .unwrap();
+        let repo = crate::FsAdrRepository::new(&adr_dir);
+        let cfg = Config {
+            adr_dir: adr_dir.clone(),
+            ..Config::default()
+        };
+
+        let reports = fix(&repo, &cfg).unwrap();
+        assert_eq!(reports.len(), 3);
+        assert!(reports[0]
+            .changes
+            .iter()
+            .any(|c| c.contains("Superseded-by")));
+        assert!(reports[1].changes[0].contains("[0001](0001-old.md)"));
+        assert_eq!(reports[2].changes, vec!["regenerated index".to_string()]);
+
+        let old = std::fs::read_to_string(adr_dir.join("0001-old.md")).unwrap();
+        assert!(old.contains("Date: "));
+        assert!(old.contains("Status: Superseded by 0002\nSuperseded-by: 0002\n"));
+        let new = std::fs::read_to_string(adr_dir.join("0002-new.md")).unwrap();
+        assert!(new.contains("Supersedes: [0001](0001-old.md)"));
+
+        assert!(lint(&repo, &cfg).unwrap().is_empty());
+        assert!(fix(&repo, &cfg).unwrap().is_empty());
+    }
+
+    #[test]
+    fn fix_restores_status_and_date_from_history_or_lifecycle() {
+        let dir = tempfile::tempdir().unwrap();
+        let adr_dir = dir.path().join("adrs");
+        std::fs::create_dir_all(&adr_dir).unwrap();
+        std::fs::write(
+            adr_dir.join("0001-a.md"),
+            "# ADR 0001: A\n\n## Context\n\nWhy.\n\n## Status History\n\n- 2024-01-01: Draft\n- 2024-03-01: Final\n",
+        )
+        .unwrap();
+        std::fs::write(adr_dir.join("0002-b.md"), "# ADR 0002: B\n\nBody\n").unwrap();
+        let repo = crate::FsAdrRepository::new(&adr_dir);
+        let cfg = Config {
+            adr_dir: adr_dir.clone(),
+            lifecycle: crate::config::Lifecycle {
+                statuses: vec!["Draft".into(), "Final".into()],
+                ..Default::default()
+            },
+            ..Config::default()
+        };
+
+        fix(&repo, &cfg).unwrap();
+        let a = std::fs::read_to_string(adr_dir.join("0001-a.md")).unwrap();
+        assert!(a.contains("Date: 2024-03-01\nStatus: Final\n"), "{}", a);
+        let b = std::fs::read_to_string(adr_dir.join("0002-b.md")).unwrap();
+        let today = Local::now().format("%Y-%m-%d").to_string();
+        assert!(
+            b.contains(&format!("Date: {}\nStatus: Draft\n", today)),
+            "{}",
+            b
+        );
+        assert!(lint(&repo, &cfg).unwrap().is_empty());
+    }
+
+    #[test]
+    fn stale_index_covers_per_tag_indexes() {
+        let dir = tempfile::tempdir().unwrap();
+        let adr_dir = dir.path().join("adrs");
+        std::fs::create_dir_all(&adr_dir).unwrap();
+        let adr = adr_dir.join("0001-pick-x.md");
+        std::fs::write(
+            &adr,
+            "# ADR 0001: Pick X\n\nDate: 2024-01-01\nStatus: Accepted\nTags: database\n\nBody\n",
+        )
+        .unwrap();
+        let repo = crate::FsAdrRepository::new(&adr_dir);
+        let cfg = Config {
+            adr_dir: adr_dir.clone(),
+            index_tags: "files".into(),
+            ..Config::default()
+        };
+        let stale = |repo: &crate::FsAdrRepository| -> Vec<String> {
+            lint(repo, &cfg)
+                .unwrap()
+                .into_iter()
+                .filter(|f| f.rule == "stale-index")
+                .map(|f| f.message)
+                .collect()
+        };
+
+        assert_eq!(
+            stale(&repo),
+            ["index.md is missing", "index-database.md is missing"]
+        );
+        assert_eq!(fix(&repo, &cfg).unwrap().len(), 1);
+        assert!(adr_dir.join("index-database.md").exists());
+        assert!(stale(&repo).is_empty());
+
+        let retagged = std::fs::read_to_string(&adr)
+            .unwrap()
+            .replace("Tags: database", "Tags: storage");
+        std::fs::write(&adr, retagged).unwrap();
+        std::fs::write(
+            adr_dir.join("index.md"),
+            render_index(&cfg, &repo.list().unwrap()),
+        )
+        .unwrap();
+        assert_eq!(
+            stale(&repo),
+            [
+                "index-storage.md is missing",
+                "index-database.md is no longer needed"
+            ]
+        );
+        fix(&repo, &cfg).unwrap();
+        assert!(!adr_dir.join("index-database.md").exists());
+        assert!(adr_dir.join("index-storage.md").exists());
+        assert!(stale(&repo).is_empty());
+    }
+
+    #[test]
+    fn reports_malformed_link_fields_and_front_matter() {
+        let adrs = vec![meta(1, "a", "A"), meta(2, "b", "B"), meta(3, "c", "C")];
+        let contents = vec![
+            "# ADR 0001: A\n\nDate: 2024-01-01\nStatus: Accepted\nSupersedes: [0002](0002-b.md), the old one\nDepends-on: 0003\n".to_string(),
+            "---\ntitle: B\nstatus: Accepted\nsupersedes: [1, two]\n---\n".to_string(),
+            "---\ntitle: [C\n---\n".to_string(),
+        ];
+        let cfg = Config::default();
+        let ctx = LintContext {
+            adrs: &adrs,
+            contents: &contents,
+            today: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            cfg: &cfg,
+            index: None,
+            tag_indexes: &[],
+        };
+        let findings: Vec<Finding> = run_rules(&ctx)
+            .into_iter()
+            .filter(|f| f.rule == "malformed-field")
+            .collect();
+        assert_eq!(findings.len(), 3);
+        assert_eq!(
+            findings[0].message,
+            "Supersedes item 'the old one' is not an ADR reference"
+        );
+        assert_eq!(
+            findings[1].message,
+            "Front matter 'supersedes' is not an ADR number or list of numbers"
+        );
+        assert!(findings[2]
+            .message
+            .starts_with("Front matter is not valid YAML"));
+    }
+
+    #[test]
+    fn detects_missing_fields_in_raw_content() {
+        assert!(declares_field("# ADR\n\nStatus: Proposed\n", "Status"));
+        assert!(declares_field("---\nstatus: Proposed\n---\n", "Status"));
+        assert!(!declares_field("# ADR\n\nBody\n", "Date"));
+        // Examples in the body do not count
+        assert!(!declares_field(
+            "# ADR\n\nDate: 2024-01-01\n\n## Decision\n\n```\nStatus: Accepted\n```\n",
+            "Status"
+        ));
+    }
+}
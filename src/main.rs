@@ -1,16 +1,31 @@
+use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, Stdio};
 
 use anyhow::{anyhow, Context, Result};
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
+use serde::Serialize;
 
 use radr::actions::{
-    accept, create_new_adr, list_and_index, mark_superseded, reformat, reformat_all, reject,
+    add_tags, create_new_adr, deprecate, link, list_and_index, mark_superseded, reformat,
+    reformat_all, remove_tags, resolve_adr, set_status, unlink,
 };
-use radr::config::load_config;
-use radr::domain::parse_number;
+use radr::config::{
+    config_schema, find_config, load_layers, set_config_value, user_config, ConfigFormat,
+    LayeredConfig, Origin,
+};
+use radr::domain::{list_contains, matches_status, parse_list, parse_number, LinkKind, People};
+use radr::graph::{self, GraphFormat};
+use radr::import::import_adr_tools;
+use radr::init::init;
+use radr::lint::{fix as lint_fix, lint, lint_file, Severity};
+use radr::output::{render, render_error, OutputFormat};
 use radr::repository::AdrRepository;
-use radr::{Config, FsAdrRepository};
+use radr::show::{section_content, show_adr, show_section};
+use radr::site::build_site;
+use radr::{AdrMeta, Config, FsAdrRepository};
 
 #[derive(Parser, Debug)]
 #[command(name = "radr", about = "Manage Architecture Decision Records (ADRs)")]
@@ -19,39 +34,188 @@ struct Cli {
     #[arg(long)]
     config: Option<PathBuf>,
 
+    /// Override a config setting for this run, e.g. --set format=mdx (repeatable)
+    #[arg(long = "set", global = true, value_name = "KEY=VALUE")]
+    set: Vec<String>,
+
+    /// Output format for command results and errors
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    output: OutputFormat,
+
     #[command(subcommand)]
     command: Commands,
 }
 
 #[derive(Subcommand, Debug)]
 enum Commands {
+    /// Set up radr: config file, ADR directory, index and a first ADR
+    #[command(
+        about = "Set up radr: config file, ADR directory, index and a first ADR",
+        long_about = "Writes a commented config file (radr.toml by default, or the --config path), creates the ADR \
+directory and its index, and adds the accepted \"0001 Record architecture decisions\" ADR. \
+Refuses to overwrite an existing config, index or ADRs unless --force is given.\n\nExamples:\n  radr init\n  radr init --dir doc/decisions --format mdx --front-matter --config-format yaml"
+    )]
+    Init(InitArgs),
     /// Create a new ADR with a title
     New {
         /// Title for the ADR
         title: String,
+        /// Tag the ADR (e.g., database). Repeat to add several
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        #[command(flatten)]
+        people: PeopleArgs,
+        /// Open the new ADR in $VISUAL/$EDITOR right away
+        #[arg(long)]
+        edit: bool,
     },
-    /// Create a new ADR that supersedes an existing ADR number
+    /// Create a new ADR that supersedes one or more existing ADRs
     Supersede {
-        /// ADR number to supersede (e.g., 0003 or 3)
+        /// ADR number(s) to supersede, comma-separated (e.g., 0003, 3 or 3,5,8)
         id: String,
         /// Title for the new ADR
         title: String,
         /// Force superseding even if already superseded
         #[arg(long)]
         force: bool,
+        #[command(flatten)]
+        people: PeopleArgs,
     },
     /// Accept an ADR by id or title
     Accept {
         /// ADR id (number) or exact title
         id_or_title: String,
+        /// Accept even if the lifecycle does not allow it
+        #[arg(long)]
+        force: bool,
+        /// Name recorded with the change in the status history
+        #[arg(long)]
+        by: Option<String>,
+        #[command(flatten)]
+        people: PeopleArgs,
     },
     /// Reject an ADR by id or title
     Reject {
         /// ADR id (number) or exact title
         id_or_title: String,
+        /// Reject even if the lifecycle does not allow it
+        #[arg(long)]
+        force: bool,
+        /// Name recorded with the change in the status history
+        #[arg(long)]
+        by: Option<String>,
+        #[command(flatten)]
+        people: PeopleArgs,
+    },
+    /// Set the status of an ADR, following the configured lifecycle
+    #[command(
+        about = "Set the status of an ADR",
+        long_about = "Moves an ADR to any status listed in the [lifecycle] config, stamps its Date \
+and appends the change to its status history. Transitions not allowed by lifecycle.transitions fail unless --force is given.\n\nExamples:\n  radr status 3 \"In Review\"\n  radr status \"Use Postgres\" accepted\n  radr status 3 Accepted --force\n  radr status 3 Accepted --by \"Ada Lovelace\""
+    )]
+    Status {
+        /// ADR id (number) or exact title
+        id_or_title: String,
+        /// New status, matched case-insensitively against lifecycle.statuses
+        status: String,
+        /// Skip the lifecycle transition check
+        #[arg(long)]
+        force: bool,
+        /// Name recorded with the change in the status history
+        #[arg(long)]
+        by: Option<String>,
+        #[command(flatten)]
+        people: PeopleArgs,
+    },
+    /// Retire an ADR without replacement
+    #[command(
+        about = "Retire an ADR without replacement",
+        long_about = "Sets the status of an ADR to Deprecated, stamps its Date and records the reason \
+in the file and the change in its status history. Use `radr supersede` when a new ADR replaces it.\n\nExamples:\n  radr deprecate 3 --reason \"We no longer run our own queue\"\n  radr deprecate \"Use Kafka\" --reason \"Moved to SQS\" --by \"Ada Lovelace\""
+    )]
+    Deprecate {
+        /// ADR id (number) or exact title
+        id_or_title: String,
+        /// Why the decision no longer applies
+        #[arg(long)]
+        reason: String,
+        /// Deprecate even if the lifecycle does not allow it
+        #[arg(long)]
+        force: bool,
+        /// Name recorded with the change in the status history
+        #[arg(long)]
+        by: Option<String>,
+        #[command(flatten)]
+        people: PeopleArgs,
+    },
+    /// Add or remove tags on an ADR
+    Tag {
+        #[command(subcommand)]
+        action: TagAction,
+    },
+    /// Add a typed link between two ADRs, with the reciprocal entry in the target
+    Link {
+        /// ADR id (number) or exact title of the linking ADR
+        from: String,
+        /// ADR id (number) or exact title of the linked ADR
+        to: String,
+        /// Relation from `from` to `to`
+        #[arg(long, value_enum)]
+        kind: LinkKind,
+    },
+    /// Remove a typed link between two ADRs, together with its reciprocal entry
+    Unlink {
+        /// ADR id (number) or exact title of the linking ADR
+        from: String,
+        /// ADR id (number) or exact title of the linked ADR
+        to: String,
+        /// Only remove this relation (default: every link from `from` to `to`)
+        #[arg(long, value_enum)]
+        kind: Option<LinkKind>,
     },
     /// List ADRs found in the ADR directory
-    List,
+    List {
+        /// Only list ADRs with this status; `active` and `inactive` (superseded,
+        /// deprecated or rejected) are also accepted. Repeat to allow several
+        #[arg(long)]
+        status: Vec<String>,
+        /// Only list ADRs with this tag (case-insensitive). Repeat to allow several
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// Only list ADRs naming this person as author, decider, consulted or
+        /// informed (case-insensitive). Repeat to allow several
+        #[arg(long = "person")]
+        people: Vec<String>,
+    },
+    /// Open an ADR in $VISUAL/$EDITOR and check it afterwards
+    #[command(
+        about = "Open an ADR in $VISUAL/$EDITOR and check it afterwards",
+        long_about = "Opens the ADR in $VISUAL, else $EDITOR (default `vi`). When the editor exits, the file is \
+parsed again and checked with the lint rules; findings are printed and the index is regenerated. \
+If errors remain (e.g. a removed Status line or a malformed Supersedes line), you are offered to reopen the editor; \
+without a terminal to ask on, the command fails instead.\n\nExamples:\n  radr edit 7\n  radr edit \"Use Postgres\"\n  radr new \"Use Postgres\" --edit"
+    )]
+    Edit {
+        /// ADR id (number) or exact title
+        id_or_title: String,
+    },
+    /// Print an ADR, one of its sections, or its parsed metadata
+    #[command(
+        about = "Print an ADR, one of its sections, or its parsed metadata",
+        long_about = "Renders the ADR for the terminal: a header with status, date, tags, people and related ADRs \
+(by number and title), then the body with styled headings, emphasis, lists and links. \
+Output goes through $PAGER (default `less`) when stdout is a terminal; set NO_COLOR to turn off styling.\n\nExamples:\n  radr show 7\n  radr show \"Use Postgres\" --section Decision\n  radr show 7 --meta --output json"
+    )]
+    Show {
+        /// ADR id (number) or exact title
+        id_or_title: String,
+        /// Print only this section (e.g., Decision), matched case-insensitively
+        #[arg(long, conflicts_with = "meta")]
+        section: Option<String>,
+        /// Print only the parsed metadata
+        #[arg(long)]
+        meta: bool,
+    },
     /// Regenerate the index.md file
     Index,
     /// Reformat ADR(s) to the current config (format/front matter)
@@ -69,91 +233,1055 @@ Cross-links in Supersedes lines and the index are updated accordingly.\n\nExampl
         #[arg(help = "ADR number to reformat; omit with --all")]
         id: Option<String>,
     },
+    /// Export the supersede graph as Mermaid, Graphviz DOT or PlantUML
+    #[command(
+        about = "Export the supersede graph as diagram source",
+        long_about = "Renders the Supersedes/Superseded-by relations as a Mermaid, Graphviz DOT or PlantUML diagram. \
+Nodes are labelled with number and title and colored by status. \
+Pass an ADR id to limit the graph to the chain around it, optionally bounded by --depth.\n\nExamples:\n  radr graph\n  radr graph 7 --depth 2 --format dot\n  radr graph --format plantuml --out docs/adr/graph.puml"
+    )]
+    Graph {
+        /// ADR number to center the graph on (e.g., 0007 or 7)
+        id: Option<String>,
+        /// Maximum number of supersede hops from the centered ADR
+        #[arg(long, requires = "id")]
+        depth: Option<usize>,
+        /// Diagram language
+        #[arg(long, value_enum, default_value_t = GraphFormat::Mermaid)]
+        format: GraphFormat,
+        /// Write the diagram to a file instead of stdout
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Import ADRs from another tool
+    Import {
+        #[command(subcommand)]
+        source: ImportSource,
+    },
+    /// Generate a static HTML site for the ADR log
+    #[command(
+        about = "Generate a static HTML site for the ADR log",
+        long_about = "Renders every ADR to HTML with links between superseding and superseded records, \
+an index page with status filter and search, and a JSON search index. Everything is generated offline.\n\nExamples:\n  radr site\n  radr site --out public/"
+    )]
+    Site {
+        /// Output directory for the generated site
+        #[arg(long, default_value = "site")]
+        out: PathBuf,
+    },
+    /// Inspect the configuration
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Check the ADR set for structural problems
+    #[command(
+        about = "Check the ADR set for structural problems",
+        long_about = "Reports duplicate numbers, dangling or asymmetric supersede links, supersede cycles, \
+filename/title mismatches, missing, invalid or future dates, and unknown statuses. \
+Exits with a non-zero code when any error is found (or any warning with --strict). \
+With --fix, safe repairs are applied first and only the remaining findings are reported.\n\nExamples:\n  radr lint\n  radr lint --strict\n  radr lint --fix"
+    )]
+    Lint {
+        /// Treat warnings as errors
+        #[arg(long)]
+        strict: bool,
+        /// Repair what can be fixed safely before reporting
+        #[arg(long)]
+        fix: bool,
+    },
 }
 
-fn main() -> Result<()> {
+/// Options of `radr init`.
+#[derive(Args, Debug)]
+struct InitArgs {
+    /// ADR directory to create, relative to the config file
+    #[arg(long, default_value = "docs/adr")]
+    dir: PathBuf,
+    /// File extension of new ADRs
+    #[arg(long, default_value = "md", value_parser = ["md", "mdx"])]
+    format: String,
+    /// Add YAML front matter to ADRs
+    #[arg(long)]
+    front_matter: bool,
+    /// Language of the config file; ignored when --config names the file
+    #[arg(long, value_enum, default_value_t = ConfigFormat::Toml)]
+    config_format: ConfigFormat,
+    /// Do not add the "Record architecture decisions" ADR
+    #[arg(long)]
+    no_first_adr: bool,
+    /// Overwrite an existing config and index
+    #[arg(long)]
+    force: bool,
+}
+
+/// People options shared by `new` and the status commands. Each may be repeated
+/// or given a comma-separated list.
+#[derive(Args, Debug, Default)]
+struct PeopleArgs {
+    /// Author of the ADR (`new` and `supersede` default to the author setting, else `git config user.name`)
+    #[arg(long = "author")]
+    authors: Vec<String>,
+    /// Person who made the decision
+    #[arg(long = "decider")]
+    deciders: Vec<String>,
+    /// Person whose opinion was sought
+    #[arg(long)]
+    consulted: Vec<String>,
+    /// Person kept up to date on the decision
+    #[arg(long)]
+    informed: Vec<String>,
+}
+
+impl PeopleArgs {
+    fn into_people(self) -> People {
+        let list = |v: Vec<String>| parse_list(&v.join(","));
+        People {
+            authors: list(self.authors),
+            deciders: list(self.deciders),
+            consulted: list(self.consulted),
+            informed: list(self.informed),
+        }
+    }
+}
+
+/// `people` with the `author` setting, else `git config user.name`, as author
+/// when none was given.
+fn with_default_author(mut people: People, cfg: &Config) -> People {
+    if people.authors.is_empty() {
+        people
+            .authors
+            .extend(cfg.author.clone().or_else(git_user_name));
+    }
+    people
+}
+
+/// `git config user.name`, when git is available and the name is set.
+fn git_user_name() -> Option<String> {
+    let out = process::Command::new("git")
+        .args(["config", "user.name"])
+        .output()
+        .ok()?;
+    let name = String::from_utf8(out.stdout).ok()?.trim().to_string();
+    (out.status.success() && !name.is_empty()).then_some(name)
+}
+
+#[derive(Subcommand, Debug)]
+enum TagAction {
+    /// Add one or more tags to an ADR
+    Add {
+        /// ADR id (number) or exact title
+        id_or_title: String,
+        /// Tags to add
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+    /// Remove one or more tags from an ADR
+    Remove {
+        /// ADR id (number) or exact title
+        id_or_title: String,
+        /// Tags to remove
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigAction {
+    /// Print the path of the config file in use
+    #[command(
+        long_about = "Prints the config file radr picked: the --config path, else RADR_CONFIG, else the first \
+radr.toml|yaml|yml|json or .radrrc.* found in the current directory or its parents, up to the repository root."
+    )]
+    Path,
+    /// Check the config for unknown keys and unsupported values
+    #[command(
+        long_about = "Checks every config layer in use (user config, project config, RADR_* variables and --set) and \
+prints every problem: unknown keys (with suggestions), unsupported values, a missing template, an index_name that looks \
+like an ADR filename, and an inconsistent lifecycle. Exits with a non-zero code when any problem is found. \
+Other commands refuse to run with an invalid config."
+    )]
+    Validate,
+    /// Print the effective configuration
+    #[command(
+        long_about = "Prints every setting after merging the defaults, the user config ($XDG_CONFIG_HOME/radr/config.toml), \
+the project config, RADR_<KEY> environment variables and --set flags, each overriding the ones before.\n\n\
+Examples:\n  radr config show\n  radr config show --origin\n  RADR_FORMAT=mdx radr config show --origin"
+    )]
+    Show {
+        /// Also print where each value comes from
+        #[arg(long)]
+        origin: bool,
+    },
+    /// Print the effective value of one setting
+    #[command(
+        long_about = "Prints the effective value of a setting, after all config layers. Strings are printed as is, \
+other values as JSON; an unset template or author prints nothing.\n\nExamples:\n  radr config get format\n  radr config get lifecycle.statuses"
+    )]
+    Get {
+        /// Config key, e.g. format or lifecycle.statuses
+        key: String,
+    },
+    /// Change one setting in the config file
+    #[command(
+        long_about = "Writes a setting to the config file in use (the one `radr config path` prints), keeping its format \
+(TOML, YAML or JSON) and key order, and for TOML and YAML its comments. Invalid settings leave the file unchanged. \
+When format, front_matter, front_matter_mode or layout change, offers to reformat the existing ADRs to match.\n\n\
+Examples:\n  radr config set format mdx\n  radr config set front_matter true --reformat"
+    )]
+    Set {
+        /// Config key, e.g. format
+        key: String,
+        /// New value; booleans accept true/false, yes/no, on/off and 1/0
+        value: String,
+        /// Reformat the existing ADRs without asking when the change affects them
+        #[arg(long)]
+        reformat: bool,
+    },
+    /// Print the JSON Schema of the config file for editor completion
+    Schema,
+}
+
+/// The config file in use, as emitted by `radr config path`.
+#[derive(Serialize)]
+struct ConfigPathRecord {
+    path: Option<PathBuf>,
+}
+
+/// Result of `radr config validate`.
+#[derive(Serialize)]
+struct ConfigValidation {
+    path: Option<PathBuf>,
+    problems: Vec<ConfigProblem>,
+}
+
+#[derive(Serialize)]
+struct ConfigProblem {
+    origin: Origin,
+    problem: String,
+}
+
+/// One effective setting, as emitted by `radr config show --origin` and
+/// `radr config get`.
+#[derive(Serialize)]
+struct ConfigValueRecord {
+    key: String,
+    value: serde_json::Value,
+    origin: Origin,
+}
+
+/// Handles `radr config show`: one `key = value` line per setting, values
+/// written as JSON, optionally followed by the layer that set them.
+fn show_config(output: OutputFormat, layers: &LayeredConfig, with_origin: bool) -> Result<()> {
+    let values = layers.config.values();
+    let records: Vec<ConfigValueRecord> = values
+        .into_iter()
+        .map(|(key, value)| ConfigValueRecord {
+            key: key.to_string(),
+            value,
+            origin: layers.origins[key].clone(),
+        })
+        .collect();
+    if !output.is_text() {
+        if with_origin {
+            emit(output, &records)?;
+        } else {
+            let map: serde_json::Map<String, serde_json::Value> =
+                records.into_iter().map(|r| (r.key, r.value)).collect();
+            emit(output, &map)?;
+        }
+        return Ok(());
+    }
+    let lines: Vec<String> = records
+        .iter()
+        .map(|r| format!("{} = {}", r.key, r.value))
+        .collect();
+    // Long values such as the lifecycle are not padded to
+    let width = lines
+        .iter()
+        .map(|l| l.chars().count())
+        .filter(|w| *w <= 40)
+        .max()
+        .unwrap_or(0);
+    for (line, record) in lines.iter().zip(&records) {
+        if with_origin {
+            println!("{:width$}  # {}", line, record.origin, width = width);
+        } else {
+            println!("{}", line);
+        }
+    }
+    Ok(())
+}
+
+#[derive(Subcommand, Debug)]
+enum ImportSource {
+    /// Import an adr-tools (npryce) directory, keeping numbers, dates and supersede links
+    #[command(name = "adr-tools")]
+    AdrTools {
+        /// Directory containing the adr-tools ADRs (e.g., doc/adr)
+        dir: PathBuf,
+    },
+}
+
+fn main() {
     let cli = Cli::parse();
-    let cfg: Config = load_config(cli.config.as_ref())?;
+    let output = cli.output;
+    if let Err(err) = run(cli) {
+        match output {
+            OutputFormat::Json | OutputFormat::Yaml => match render_error(output, &err) {
+                Ok(s) => eprint!("{}", s),
+                Err(_) => eprintln!("Error: {:?}", err),
+            },
+            OutputFormat::Text | OutputFormat::Csv => eprintln!("Error: {:?}", err),
+        }
+        process::exit(1);
+    }
+}
 
-    fs::create_dir_all(&cfg.adr_dir)
-        .with_context(|| format!("Creating ADR directory at {}", cfg.adr_dir.display()))?;
+/// Regenerates the index and prints the ADRs matching any of the `status` filters,
+/// any of the `tags` and any of the `people` (all ADRs when empty).
+fn print_list(
+    repo: &FsAdrRepository,
+    cfg: &Config,
+    output: OutputFormat,
+    status: &[String],
+    tags: &[String],
+    people: &[String],
+) -> Result<()> {
+    let mut adrs = list_and_index(repo, cfg)?;
+    if !status.is_empty() {
+        adrs.retain(|a| status.iter().any(|s| matches_status(&a.status, s)));
+    }
+    if !tags.is_empty() {
+        adrs.retain(|a| tags.iter().any(|t| list_contains(&a.tags, t)));
+    }
+    if !people.is_empty() {
+        adrs.retain(|a| people.iter().any(|p| a.people.contains(p)));
+    }
+    if !emit(output, &adrs)? {
+        for a in &adrs {
+            println!("{:04} | {} | {} | {}", a.number, a.title, a.status, a.date);
+        }
+        println!("Updated {}", cfg.adr_dir.join(&cfg.index_name).display());
+    }
+    Ok(())
+}
 
-    let repo = FsAdrRepository::new(&cfg.adr_dir);
+/// Opens `path` in `$VISUAL`, else `$EDITOR`, else `vi`, and waits for it to exit.
+fn open_editor(path: &Path) -> Result<()> {
+    let editor = ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let status = process::Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .with_context(|| format!("Starting editor '{}'", editor))?;
+    if !status.success() {
+        return Err(anyhow!("Editor '{}' exited with {}", editor, status));
+    }
+    Ok(())
+}
 
-    match cli.command {
-        Commands::New { title } => {
-            let meta = create_new_adr(&repo, &cfg, &title, None)?;
+/// Asks a yes/no question on stderr; an empty answer means yes.
+fn confirm(question: &str) -> Result<bool> {
+    eprint!("{} [Y/n] ", question);
+    std::io::stderr().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    let answer = answer.trim().to_ascii_lowercase();
+    Ok(answer.is_empty() || answer == "y" || answer == "yes")
+}
+
+/// Opens the ADR at `path` in the editor, then re-parses it and prints the lint
+/// findings about it. While errors remain, offers to reopen the editor when stdin
+/// is a terminal and fails otherwise. The index is regenerated either way.
+fn edit_adr_file(repo: &FsAdrRepository, cfg: &Config, path: &Path) -> Result<AdrMeta> {
+    loop {
+        open_editor(path)?;
+        let findings = lint_file(repo, cfg, path)?;
+        let file = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        for f in &findings {
+            eprintln!("{}: {}[{}] {}", file, f.severity, f.rule, f.message);
+        }
+        let errors = findings
+            .iter()
+            .filter(|f| f.severity == Severity::Error)
+            .count();
+        if errors == 0 {
+            break;
+        }
+        if std::io::stdin().is_terminal()
+            && confirm(&format!(
+                "{} has {} error(s). Reopen the editor?",
+                file, errors
+            ))?
+        {
+            continue;
+        }
+        list_and_index(repo, cfg)?;
+        return Err(anyhow!("{} has {} error(s) after editing", file, errors));
+    }
+    list_and_index(repo, cfg)?;
+    repo.parse_adr_file(path)
+}
+
+/// Sends `text` through `$PAGER` (default `less` with `LESS=FRX`) when stdout is
+/// a terminal, else prints it directly. Falls back to printing if the pager
+/// cannot be started.
+fn page(text: &str) -> Result<()> {
+    if std::io::stdout().is_terminal() {
+        let pager = env::var("PAGER")
+            .ok()
+            .filter(|p| !p.trim().is_empty())
+            .unwrap_or_else(|| "less".to_string());
+        let mut parts = pager.split_whitespace();
+        if let Some(program) = parts.next() {
+            let mut cmd = process::Command::new(program);
+            cmd.args(parts).stdin(Stdio::piped());
+            if env::var_os("LESS").is_none() {
+                cmd.env("LESS", "FRX");
+            }
+            if let Ok(mut child) = cmd.spawn() {
+                if let Some(mut stdin) = child.stdin.take() {
+                    // The user may quit the pager before reading everything
+                    let _ = stdin.write_all(text.as_bytes());
+                }
+                child.wait().context("Waiting for pager")?;
+                return Ok(());
+            }
+        }
+    }
+    print!("{}", text);
+    Ok(())
+}
+
+/// Whether terminal output should be styled: stdout is a terminal and
+/// `NO_COLOR` is unset.
+fn use_color() -> bool {
+    std::io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+}
+
+/// One ADR section, as emitted by `radr show --section` in structured output.
+#[derive(Serialize)]
+struct SectionRecord {
+    number: u32,
+    title: String,
+    section: String,
+    content: String,
+}
+
+/// Prints `value` in the selected structured format; returns false for text output
+/// so the caller prints its human-readable message instead.
+fn emit<T: Serialize + ?Sized>(output: OutputFormat, value: &T) -> Result<bool> {
+    if output.is_text() {
+        return Ok(false);
+    }
+    print!("{}", render(output, value)?);
+    Ok(true)
+}
+
+/// Settings that change how ADR files are written, so existing ADRs no longer
+/// match after changing them.
+const REFORMAT_KEYS: &[&str] = &["format", "front_matter", "front_matter_mode", "layout"];
+
+/// Result of `radr config set`.
+#[derive(Serialize)]
+struct ConfigSetRecord {
+    path: PathBuf,
+    key: String,
+    value: serde_json::Value,
+    reformatted: usize,
+}
+
+/// Handles `radr config set`, which runs before loading the config so that it
+/// can also repair an invalid one.
+fn run_config_set(
+    output: OutputFormat,
+    config: Option<&PathBuf>,
+    overrides: &[String],
+    key: &str,
+    value: &str,
+    reformat: bool,
+) -> Result<()> {
+    let path = find_config(config)?
+        .ok_or_else(|| anyhow!("No config file found; run `radr init` to create one"))?;
+    let before = load_layers(config, overrides)
+        .ok()
+        .and_then(|l| l.get(key).ok())
+        .map(|(v, _)| v);
+    let value = set_config_value(&path, key, value)?;
+    if output.is_text() {
+        println!("Set {} = {} in {}", key, value, path.display());
+    }
+
+    let layers = load_layers(config, overrides)?;
+    let (effective, origin) = layers.get(key)?;
+    if origin != Origin::Project(path.clone()) {
+        eprintln!("Warning: {} is overridden by {}", key, origin);
+    }
+    let mut reformatted = 0;
+    if REFORMAT_KEYS.contains(&key) && before.as_ref() != Some(&effective) {
+        layers.check()?;
+        let cfg = layers.config;
+        let repo = FsAdrRepository::new(&cfg.adr_dir);
+        let count = if cfg.adr_dir.is_dir() {
+            repo.list()?.len()
+        } else {
+            0
+        };
+        if count > 0 {
+            let question = format!("Reformat {} ADR(s) to match?", count);
+            if reformat || (std::io::stdin().is_terminal() && confirm(&question)?) {
+                let (updated, warnings) = reformat_all(&repo, &cfg)?;
+                for w in &warnings {
+                    eprintln!("Warning: {}", w);
+                }
+                reformatted = updated.len();
+                if output.is_text() {
+                    println!(
+                        "Reformatted {} ADR(s) to {} (front matter: {})",
+                        reformatted, cfg.format, cfg.front_matter
+                    );
+                }
+            } else {
+                eprintln!(
+                    "Run `radr reformat --all` to update the {} existing ADR(s)",
+                    count
+                );
+            }
+        }
+    }
+    emit(
+        output,
+        &ConfigSetRecord {
+            path,
+            key: key.to_string(),
+            value,
+            reformatted,
+        },
+    )?;
+    Ok(())
+}
+
+/// Handles `radr init`, which runs before (and instead of) loading a config.
+fn run_init(output: OutputFormat, config: Option<PathBuf>, args: InitArgs) -> Result<()> {
+    let (config_path, config_format) = match config {
+        Some(path) => {
+            let format = ConfigFormat::from_path(&path)?;
+            (path, format)
+        }
+        None => (
+            PathBuf::from(args.config_format.file_name()),
+            args.config_format,
+        ),
+    };
+    let cfg = Config {
+        adr_dir: config_path.parent().unwrap_or(Path::new("")).join(args.dir),
+        format: args.format,
+        front_matter: args.front_matter,
+        ..Config::default()
+    };
+    let repo = FsAdrRepository::new(&cfg.adr_dir);
+    let people = People {
+        authors: git_user_name().into_iter().collect(),
+        ..People::default()
+    };
+    let report = init(
+        &repo,
+        &cfg,
+        &config_path,
+        config_format,
+        (!args.no_first_adr).then_some(&people),
+        args.force,
+    )?;
+    if !emit(output, &report)? {
+        println!("Wrote {}", report.config.display());
+        println!(
+            "Created {} with {}",
+            report.adr_dir.display(),
+            report.index.display()
+        );
+        if let Some(a) = &report.first_adr {
             println!(
                 "Created ADR {:04}: {} at {}",
-                meta.number,
-                meta.title,
-                meta.path.display()
+                a.number,
+                a.title,
+                a.path.display()
             );
         }
-        Commands::Supersede { id, title, force } => {
-            let old_num = parse_number(&id)?;
-            // Pre-check: if target ADR is already superseded, print helpful message and exit with error
-            if !force {
-                if let Ok(existing) = repo.list() {
-                    if let Some(old) = existing.iter().find(|a| a.number == old_num) {
-                        if let Some(sb) = old.superseded_by {
-                            let sb_title = existing
-                                .iter()
-                                .find(|a| a.number == sb)
-                                .map(|a| a.title.as_str())
-                                .unwrap_or("");
-                            eprintln!(
-                                "{:04}: {} is already superseded by {:04}: {}",
-                                old.number, old.title, sb, sb_title
-                            );
-                            return Err(anyhow!("ADR already superseded"));
-                        }
+    }
+    Ok(())
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let output = cli.output;
+    match cli.command {
+        Commands::Init(args) => return run_init(output, cli.config, args),
+        Commands::Config {
+            action: ConfigAction::Path,
+        } => {
+            let path = find_config(cli.config.as_ref())?;
+            if !emit(output, &ConfigPathRecord { path: path.clone() })? {
+                match path {
+                    Some(p) => println!("{}", p.display()),
+                    None => println!("No config file found; using defaults"),
+                }
+            }
+            return Ok(());
+        }
+        Commands::Config {
+            action: ConfigAction::Validate,
+        } => {
+            let path = find_config(cli.config.as_ref())?;
+            let layers = load_layers(cli.config.as_ref(), &cli.set)?;
+            let report = ConfigValidation {
+                path: path.clone(),
+                problems: layers
+                    .problems
+                    .into_iter()
+                    .map(|(origin, problem)| ConfigProblem { origin, problem })
+                    .collect(),
+            };
+            if !emit(output, &report)? {
+                let files: Vec<PathBuf> = user_config().into_iter().chain(path).collect();
+                for p in &report.problems {
+                    println!("{}: {}", p.origin, p.problem);
+                }
+                if report.problems.is_empty() {
+                    if files.is_empty() {
+                        println!("No config file found; using defaults");
+                    }
+                    for f in &files {
+                        println!("{} is valid", f.display());
                     }
                 }
             }
+            if !report.problems.is_empty() {
+                return Err(anyhow!("Config has {} problem(s)", report.problems.len()));
+            }
+            return Ok(());
+        }
+        Commands::Config {
+            action:
+                ConfigAction::Set {
+                    ref key,
+                    ref value,
+                    reformat,
+                },
+        } => {
+            return run_config_set(output, cli.config.as_ref(), &cli.set, key, value, reformat);
+        }
+        Commands::Config {
+            action: ConfigAction::Schema,
+        } => {
+            let schema = config_schema();
+            if !emit(output, &schema)? {
+                println!("{}", serde_json::to_string_pretty(&schema)?);
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+    let layers = load_layers(cli.config.as_ref(), &cli.set)?;
+    layers.check()?;
+    if let Commands::Config {
+        action: ConfigAction::Show { origin },
+    } = cli.command
+    {
+        return show_config(output, &layers, origin);
+    }
+    if let Commands::Config {
+        action: ConfigAction::Get { key },
+    } = &cli.command
+    {
+        let (value, origin) = layers.get(key)?;
+        let record = ConfigValueRecord {
+            key: key.clone(),
+            value,
+            origin,
+        };
+        if !emit(output, &record)? {
+            match &record.value {
+                serde_json::Value::String(s) => println!("{}", s),
+                serde_json::Value::Null => {}
+                v => println!("{}", v),
+            }
+        }
+        return Ok(());
+    }
+    let cfg: Config = layers.config;
 
-            let new_meta = create_new_adr(&repo, &cfg, &title, Some(old_num))?;
-            mark_superseded(&repo, &cfg, old_num, new_meta.number)?;
-            println!(
-                "Created ADR {:04} superseding {:04}",
-                new_meta.number, old_num
-            );
+    // Only commands that add ADRs may start a new ADR directory
+    let creates_adrs = matches!(cli.command, Commands::New { .. } | Commands::Import { .. });
+    if !creates_adrs && !cfg.adr_dir.is_dir() {
+        return Err(anyhow!(
+            "ADR directory {} does not exist; run `radr init` to set it up",
+            cfg.adr_dir.display()
+        ));
+    }
+
+    let repo = FsAdrRepository::new(&cfg.adr_dir);
+
+    match cli.command {
+        Commands::Init(_) | Commands::Config { .. } => {
+            unreachable!("handled before loading the config")
         }
-        Commands::Accept { id_or_title } => {
-            let updated = accept(&repo, &cfg, &id_or_title)?;
-            println!("Accepted ADR {:04}: {}", updated.number, updated.title);
+        Commands::New {
+            title,
+            tags,
+            people,
+            edit,
+        } => {
+            let people = with_default_author(people.into_people(), &cfg);
+            let mut meta = create_new_adr(&repo, &cfg, &title, &[], &tags, &people)?;
+            if edit {
+                meta = edit_adr_file(&repo, &cfg, &meta.path)?;
+            }
+            if !emit(output, &meta)? {
+                println!(
+                    "Created ADR {:04}: {} at {}",
+                    meta.number,
+                    meta.title,
+                    meta.path.display()
+                );
+            }
         }
-        Commands::Reject { id_or_title } => {
-            let updated = reject(&repo, &cfg, &id_or_title)?;
-            println!("Rejected ADR {:04}: {}", updated.number, updated.title);
+        Commands::Supersede {
+            id,
+            title,
+            force,
+            people,
+        } => {
+            let mut old_nums = id
+                .split(',')
+                .map(parse_number)
+                .collect::<Result<Vec<u32>>>()?;
+            // `3,3` supersedes ADR 3 once
+            old_nums.sort_unstable();
+            old_nums.dedup();
+            // Pre-check: every target must exist and, unless forced, not be superseded yet
+            let existing = repo.list()?;
+            for &old_num in &old_nums {
+                let old = existing
+                    .iter()
+                    .find(|a| a.number == old_num)
+                    .ok_or_else(|| anyhow!("Could not find ADR {:04} to supersede", old_num))?;
+                if let (false, Some(sb)) = (force, old.superseded_by) {
+                    let sb_title = existing
+                        .iter()
+                        .find(|a| a.number == sb)
+                        .map(|a| a.title.as_str())
+                        .unwrap_or("");
+                    return Err(anyhow!(
+                        "{:04}: {} is already superseded by {:04}: {}",
+                        old.number,
+                        old.title,
+                        sb,
+                        sb_title
+                    ));
+                }
+            }
+
+            let people = with_default_author(people.into_people(), &cfg);
+            let new_meta = create_new_adr(&repo, &cfg, &title, &old_nums, &[], &people)?;
+            for &old_num in &old_nums {
+                mark_superseded(&repo, &cfg, old_num, new_meta.number)?;
+            }
+            if !emit(output, &new_meta)? {
+                println!(
+                    "Created ADR {:04} superseding {}",
+                    new_meta.number,
+                    old_nums
+                        .iter()
+                        .map(|n| format!("{:04}", n))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
         }
-        Commands::List | Commands::Index => {
-            let adrs = list_and_index(&repo, &cfg)?;
-            for a in &adrs {
-                println!("{:04} | {} | {} | {}", a.number, a.title, a.status, a.date);
+        Commands::Accept {
+            id_or_title,
+            force,
+            by,
+            people,
+        } => {
+            let updated = set_status(
+                &repo,
+                &cfg,
+                &id_or_title,
+                "Accepted",
+                force,
+                by.as_deref(),
+                &people.into_people(),
+            )?;
+            if !emit(output, &updated)? {
+                println!("Accepted ADR {:04}: {}", updated.number, updated.title);
             }
-            println!("Updated {}", cfg.adr_dir.join(&cfg.index_name).display());
         }
-        Commands::Reformat { all, id } => {
-            if all {
-                let updated = reformat_all(&repo, &cfg)?;
+        Commands::Reject {
+            id_or_title,
+            force,
+            by,
+            people,
+        } => {
+            let updated = set_status(
+                &repo,
+                &cfg,
+                &id_or_title,
+                "Rejected",
+                force,
+                by.as_deref(),
+                &people.into_people(),
+            )?;
+            if !emit(output, &updated)? {
+                println!("Rejected ADR {:04}: {}", updated.number, updated.title);
+            }
+        }
+        Commands::Status {
+            id_or_title,
+            status,
+            force,
+            by,
+            people,
+        } => {
+            let updated = set_status(
+                &repo,
+                &cfg,
+                &id_or_title,
+                &status,
+                force,
+                by.as_deref(),
+                &people.into_people(),
+            )?;
+            if !emit(output, &updated)? {
                 println!(
-                    "Reformatted {} ADR(s) to {} (front matter: {})",
-                    updated.len(),
-                    cfg.format,
-                    cfg.front_matter
+                    "ADR {:04}: {} is now {}",
+                    updated.number, updated.title, updated.status
                 );
+            }
+        }
+        Commands::Deprecate {
+            id_or_title,
+            reason,
+            force,
+            by,
+            people,
+        } => {
+            let updated = deprecate(
+                &repo,
+                &cfg,
+                &id_or_title,
+                &reason,
+                force,
+                by.as_deref(),
+                &people.into_people(),
+            )?;
+            if !emit(output, &updated)? {
+                println!("Deprecated ADR {:04}: {}", updated.number, updated.title);
+            }
+        }
+        Commands::Link { from, to, kind } => {
+            let (from, to) = link(&repo, &cfg, &from, &to, kind)?;
+            if !emit(output, &from)? {
+                println!("ADR {:04} {} {:04}", from.number, kind.phrase(), to.number);
+            }
+        }
+        Commands::Unlink { from, to, kind } => {
+            let (from, to) = unlink(&repo, &cfg, &from, &to, kind)?;
+            if !emit(output, &from)? {
+                println!(
+                    "Removed link from ADR {:04} to {:04}",
+                    from.number, to.number
+                );
+            }
+        }
+        Commands::Tag { action } => {
+            let updated = match action {
+                TagAction::Add { id_or_title, tags } => add_tags(&repo, &cfg, &id_or_title, &tags)?,
+                TagAction::Remove { id_or_title, tags } => {
+                    remove_tags(&repo, &cfg, &id_or_title, &tags)?
+                }
+            };
+            if !emit(output, &updated)? {
+                let tags = if updated.tags.is_empty() {
+                    "none".to_string()
+                } else {
+                    updated.tags.join(", ")
+                };
+                println!("ADR {:04} tags: {}", updated.number, tags);
+            }
+        }
+        Commands::List {
+            status,
+            tags,
+            people,
+        } => print_list(&repo, &cfg, output, &status, &tags, &people)?,
+        Commands::Edit { id_or_title } => {
+            let adr = resolve_adr(&repo, &id_or_title)?;
+            let updated = edit_adr_file(&repo, &cfg, &adr.path)?;
+            if !emit(output, &updated)? {
+                println!("Edited ADR {:04}: {}", updated.number, updated.title);
+            }
+        }
+        Commands::Show {
+            id_or_title,
+            section,
+            meta,
+        } => {
+            let adr = resolve_adr(&repo, &id_or_title)?;
+            if meta {
+                if !emit(output, &adr)? {
+                    print!("{}", render(OutputFormat::Yaml, &adr)?);
+                }
+            } else if let Some(name) = section {
+                if output.is_text() {
+                    page(&show_section(&repo, &adr, &name, use_color())?)?;
+                } else {
+                    let (section, content) = section_content(&repo, &adr, &name)?;
+                    let record = SectionRecord {
+                        number: adr.number,
+                        title: adr.title.clone(),
+                        section,
+                        content,
+                    };
+                    emit(output, &record)?;
+                }
+            } else if !emit(output, &adr)? {
+                page(&show_adr(&repo, &adr, use_color())?)?;
+            }
+        }
+        Commands::Index => print_list(&repo, &cfg, output, &[], &[], &[])?,
+        Commands::Reformat { all, id } => {
+            if all {
+                let (updated, warnings) = reformat_all(&repo, &cfg)?;
+                for w in &warnings {
+                    eprintln!("Warning: {}", w);
+                }
+                if !emit(output, &updated)? {
+                    println!(
+                        "Reformatted {} ADR(s) to {} (front matter: {})",
+                        updated.len(),
+                        cfg.format,
+                        cfg.front_matter
+                    );
+                }
             } else {
                 let id =
                     id.ok_or_else(|| anyhow::anyhow!("Missing ADR id. Pass an id or use --all"))?;
                 let n = parse_number(&id)?;
-                let updated = reformat(&repo, &cfg, n)?;
+                let (updated, warnings) = reformat(&repo, &cfg, n)?;
+                for w in &warnings {
+                    eprintln!("Warning: {}", w);
+                }
+                if !emit(output, &updated)? {
+                    println!(
+                        "Reformatted ADR {:04}: {} to {} (front matter: {})",
+                        updated.number, updated.title, cfg.format, cfg.front_matter
+                    );
+                }
+            }
+        }
+        Commands::Graph {
+            id,
+            depth,
+            format,
+            out,
+        } => {
+            let mut adrs = repo.list()?;
+            if let Some(id) = id {
+                let n = parse_number(&id)?;
+                if !adrs.iter().any(|a| a.number == n) {
+                    return Err(anyhow!("ADR not found by id: {:04}", n));
+                }
+                adrs = graph::select(&adrs, n, depth);
+            }
+            let source = graph::render(format, &adrs);
+            match out {
+                Some(path) => {
+                    fs::write(&path, &source)
+                        .with_context(|| format!("Writing graph to {}", path.display()))?;
+                    if !emit(output, &adrs)? {
+                        println!("Wrote graph of {} ADR(s) to {}", adrs.len(), path.display());
+                    }
+                }
+                None => {
+                    if !emit(output, &adrs)? {
+                        print!("{}", source);
+                    }
+                }
+            }
+        }
+        Commands::Import { source } => match source {
+            ImportSource::AdrTools { dir } => {
+                let imported = import_adr_tools(&repo, &cfg, &dir)?;
+                if !emit(output, &imported)? {
+                    for a in &imported {
+                        println!("Imported ADR {:04}: {} ({})", a.number, a.title, a.status);
+                    }
+                    println!("Imported {} ADR(s) from {}", imported.len(), dir.display());
+                }
+            }
+        },
+        Commands::Site { out } => {
+            let written = build_site(&repo, &cfg, &out)?;
+            if !emit(output, &written)? {
                 println!(
-                    "Reformatted ADR {:04}: {} to {} (front matter: {})",
-                    updated.number, updated.title, cfg.format, cfg.front_matter
+                    "Generated site with {} file(s) at {}",
+                    written.len(),
+                    out.display()
                 );
             }
         }
+        Commands::Lint { strict, fix } => {
+            if fix {
+                let reports = lint_fix(&repo, &cfg)?;
+                for r in &reports {
+                    let file = r
+                        .path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().into_owned())
+                        .unwrap_or_default();
+                    // Keep stdout parseable when a structured format is selected
+                    if output.is_text() {
+                        println!("Fixed {}: {}", file, r.changes.join("; "));
+                    } else {
+                        eprintln!("Fixed {}: {}", file, r.changes.join("; "));
+                    }
+                }
+                if output.is_text() {
+                    println!("Fixed {} file(s)", reports.len());
+                }
+            }
+            let findings = lint(&repo, &cfg)?;
+            let errors = findings
+                .iter()
+                .filter(|f| f.severity == Severity::Error)
+                .count();
+            let warnings = findings.len() - errors;
+            if !emit(output, &findings)? {
+                for f in &findings {
+                    let file = f
+                        .path
+                        .as_ref()
+                        .and_then(|p| p.file_name())
+                        .map(|n| n.to_string_lossy().into_owned())
+                        .unwrap_or_else(|| cfg.adr_dir.display().to_string());
+                    println!("{}: {}[{}] {}", file, f.severity, f.rule, f.message);
+                }
+                println!("{} error(s), {} warning(s)", errors, warnings);
+            }
+            if errors > 0 || (strict && warnings > 0) {
+                return Err(anyhow!("Lint failed"));
+            }
+        }
     }
 
     Ok(())
@@ -0,0 +1,157 @@
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Output format selected with the global `--output` flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text (default)
+    #[default]
+    Text,
+    Json,
+    Yaml,
+    Csv,
+}
+
+impl OutputFormat {
+    pub fn is_text(self) -> bool {
+        self == OutputFormat::Text
+    }
+}
+
+/// Renders a record (or a list of records) in a structured format.
+/// CSV writes one row per record with a header taken from the first record's fields.
+pub fn render<T: Serialize + ?Sized>(format: OutputFormat, value: &T) -> Result<String> {
+    match format {
+        OutputFormat::Json => {
+            let mut s = serde_json::to_string_pretty(value)?;
+            s.push('\n');
+            Ok(s)
+        }
+        OutputFormat::Yaml => Ok(serde_yaml::to_string(value)?),
+        OutputFormat::Csv => render_csv(&serde_json::to_value(value)?),
+        OutputFormat::Text => Err(anyhow!("Text output has no structured rendering")),
+    }
+}
+
+/// Renders an error as a structured object: `{"error": "...", "causes": [...]}`.
+pub fn render_error(format: OutputFormat, err: &anyhow::Error) -> Result<String> {
+    #[derive(Serialize)]
+    struct ErrorRecord {
+        error: String,
+        causes: Vec<String>,
+    }
+    let record = ErrorRecord {
+        error: err.to_string(),
+        causes: err.chain().skip(1).map(|c| c.to_string()).collect(),
+    };
+    render(format, &record)
+}
+
+fn render_csv(value: &Value) -> Result<String> {
+    let rows: Vec<&serde_json::Map<String, Value>> = match value {
+        Value::Array(items) => items
+            .iter()
+            .map(|v| {
+                v.as_object()
+                    .ok_or_else(|| anyhow!("CSV output requires records"))
+            })
+            .collect::<Result<_>>()?,
+        Value::Object(map) => vec![map],
+        _ => return Err(anyhow!("CSV output requires records")),
+    };
+    let mut out = String::new();
+    let Some(first) = rows.first() else {
+        return Ok(out);
+    };
+    let headers: Vec<&String> = first.keys().collect();
+    out.push_str(
+        &headers
+            .iter()
+            .map(|h| csv_field(h))
+            .collect::<Vec<_>>()
+            .join(","),
+    );
+    out.push('\n');
+    for row in rows {
+        let line = headers
+            .iter()
+            .map(|h| csv_field(&csv_value(row.get(*h).unwrap_or(&Value::Null))))
+            .collect::<Vec<_>>()
+            .join(",");
+        out.push_str(&line);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+fn csv_value(v: &Value) -> String {
+    match v {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().map(csv_value).collect::<Vec<_>>().join(";"),
+        other => other.to_string(),
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains(',') || s.contains('"') || s.contains('\n') {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Rec {
+        number: u32,
+        title: String,
+        supersedes: Option<u32>,
+    }
+
+    fn recs() -> Vec<Rec> {
+        vec![
+            Rec {
+                number: 1,
+                title: "Use \"quotes\", commas".into(),
+                supersedes: None,
+            },
+            Rec {
+                number: 2,
+                title: "Plain".into(),
+                supersedes: Some(1),
+            },
+        ]
+    }
+
+    #[test]
+    fn csv_has_header_and_escapes_fields() {
+        let out = render(OutputFormat::Csv, &recs()).unwrap();
+        assert_eq!(
+            out,
+            "number,title,supersedes\n1,\"Use \"\"quotes\"\", commas\",\n2,Plain,1\n"
+        );
+    }
+
+    #[test]
+    fn json_and_yaml_keep_field_order() {
+        let json = render(OutputFormat::Json, &recs()[1]).unwrap();
+        assert!(json.find("number").unwrap() < json.find("supersedes").unwrap());
+        let yaml = render(OutputFormat::Yaml, &recs()).unwrap();
+        assert!(yaml.starts_with("- number: 1\n"));
+    }
+
+    #[test]
+    fn error_renders_message_and_causes() {
+        let err = anyhow!("inner").context("outer");
+        let json = render_error(OutputFormat::Json, &err).unwrap();
+        let v: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(v["error"], "outer");
+        assert_eq!(v["causes"][0], "inner");
+    }
+}
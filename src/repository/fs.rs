@@ -1,16 +1,37 @@
 use anyhow::{anyhow, Context, Result};
-use chrono::Local;
+use chrono::{Local, NaiveDate};
 use regex::Regex;
 use std::{
     ffi::OsStr,
     fs,
     fs::File,
-    io::{BufRead, BufReader, Write},
+    io::Write,
     path::{Path, PathBuf},
 };
 
-use super::AdrRepository;
-use crate::domain::AdrMeta;
+use super::{AdrRepository, ADR_FILE_PATTERN};
+use crate::document::AdrDocument;
+use crate::domain::{
+    decision_date, parse_adr_ref, parse_adr_refs, parse_list, AdrLink, AdrMeta, LinkKind, People,
+    Role,
+};
+
+/// ADR numbers in front matter: `supersedes: 3` or `supersedes: [3, 5]`.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum Refs {
+    One(u32),
+    Many(Vec<u32>),
+}
+
+impl Refs {
+    fn into_vec(self) -> Vec<u32> {
+        match self {
+            Refs::One(n) => vec![n],
+            Refs::Many(v) => v,
+        }
+    }
+}
 
 pub struct FsAdrRepository {
     root: PathBuf,
@@ -21,90 +42,130 @@ impl FsAdrRepository {
         Self { root: root.into() }
     }
 
-    fn parse_adr_file(&self, path: &Path) -> Result<AdrMeta> {
+    /// Parses the ADR at `path`. Missing fields fall back to defaults (status
+    /// Accepted, today's date, the title from the filename); use lint to find them.
+    pub fn parse_adr_file(&self, path: &Path) -> Result<AdrMeta> {
         let mut number = self.number_from_filename(path).unwrap_or(0);
         let mut title = String::new();
-        let mut status = String::from("Accepted");
+        let mut status: Option<String> = None;
         let mut date = String::new();
-        let mut supersedes: Option<u32> = None;
+        let mut supersedes: Vec<u32> = Vec::new();
         let mut superseded_by: Option<u32> = None;
+        let mut deprecation_reason: Option<String> = None;
 
         let raw = fs::read_to_string(path)?;
-        // Try front matter first
-        if let Some(stripped) = raw.strip_prefix("---\n") {
-            if let Some(end) = stripped.find("\n---\n") {
-                let fm_block = &stripped[..end];
-                #[derive(serde::Deserialize)]
-                struct FM {
-                    title: Option<String>,
-                    date: Option<String>,
-                    status: Option<String>,
-                    number: Option<u32>,
-                    supersedes: Option<u32>,
-                    superseded_by: Option<u32>,
-                }
-                if let Ok(fm) = serde_yaml::from_str::<FM>(fm_block) {
-                    if let Some(n) = fm.number {
-                        number = n;
-                    }
-                    if let Some(t) = fm.title {
-                        title = t;
-                    }
-                    if let Some(d) = fm.date {
-                        date = d;
-                    }
-                    if let Some(s) = fm.status {
-                        status = s;
-                    }
-                    if let Some(su) = fm.supersedes {
-                        supersedes = Some(su);
-                    }
-                    if let Some(sb) = fm.superseded_by {
-                        superseded_by = Some(sb);
-                    }
-                }
+        let doc = AdrDocument::parse(&raw);
+        // Front matter first
+        if let Some(fm_block) = doc.front_matter() {
+            #[derive(serde::Deserialize)]
+            struct FM {
+                title: Option<String>,
+                date: Option<String>,
+                status: Option<String>,
+                number: Option<u32>,
+                supersedes: Option<Refs>,
+                superseded_by: Option<u32>,
+                deprecation_reason: Option<String>,
             }
-        }
-
-        if title.is_empty() || date.is_empty() || status.is_empty() {
-            // Fallback scan lines for classic format
-            let reader = BufReader::new(raw.as_bytes());
-            for (i, line) in reader.lines().take(200).enumerate() {
-                let line = line?;
-                if i == 0 {
-                    if let Some(idx) = line.find(": ") {
-                        let head = &line[..idx];
-                        if let Some(num_idx) = head.rfind(' ') {
-                            if let Ok(n) = head[num_idx + 1..].parse::<u32>() {
-                                number = n;
-                            }
-                        }
-                        title = line[idx + 2..].trim().to_string();
-                    }
+            if let Ok(fm) = serde_yaml::from_str::<FM>(fm_block) {
+                if let Some(n) = fm.number {
+                    number = n;
                 }
-                if let Some(stripped) = line.strip_prefix("Title:") {
-                    title = stripped.trim().to_string();
+                if let Some(t) = fm.title {
+                    title = t;
                 }
-                if let Some(stripped) = line.strip_prefix("Date:") {
-                    date = stripped.trim().to_string();
+                if let Some(d) = fm.date {
+                    date = d;
                 }
-                if let Some(stripped) = line.strip_prefix("Status:") {
-                    status = stripped.trim().to_string();
+                if let Some(s) = fm.status {
+                    status = Some(s);
                 }
-                if let Some(stripped) = line.strip_prefix("Supersedes:") {
-                    let v = stripped.trim();
-                    if let Ok(n) = v.parse::<u32>() {
-                        supersedes = Some(n);
-                    }
+                supersedes = fm.supersedes.map(Refs::into_vec).unwrap_or_default();
+                superseded_by = fm.superseded_by;
+                deprecation_reason = fm.deprecation_reason;
+            }
+        }
+
+        // Then the `# ADR NNNN: Title` heading and the metadata block under it; body
+        // lines are never read as metadata
+        if let Some(h) = doc.heading() {
+            if let Some((head, t)) = h.split_once(": ") {
+                if let Some(n) = head.rsplit(' ').next().and_then(|n| n.parse::<u32>().ok()) {
+                    number = n;
                 }
-                if let Some(stripped) = line.strip_prefix("Superseded-by:") {
-                    let v = stripped.trim();
-                    if let Ok(n) = v.parse::<u32>() {
-                        superseded_by = Some(n);
-                    }
+                if title.is_empty() {
+                    title = t.trim().to_string();
                 }
+            } else if title.is_empty() && doc.front_matter().is_some() {
+                // MADR keeps the title in a plain H1 below the front matter
+                title = h.to_string();
+            }
+        }
+        if let Some(t) = doc.field("Title") {
+            if doc.front_matter().is_none() || title.is_empty() {
+                title = t.to_string();
+            }
+        }
+        if date.is_empty() {
+            if let Some(d) = doc.field("Date") {
+                date = d.to_string();
+            }
+        }
+        if status.is_none() {
+            status = doc.field("Status").map(str::to_string);
+        }
+        if supersedes.is_empty() {
+            if let Some(v) = doc.field("Supersedes") {
+                supersedes = parse_adr_refs(v);
             }
         }
+        if superseded_by.is_none() {
+            superseded_by = doc.field("Superseded-by").and_then(parse_adr_ref);
+        }
+        let fm_map = match doc.front_matter_yaml() {
+            Some(Ok(serde_yaml::Value::Mapping(map))) => map,
+            _ => serde_yaml::Mapping::new(),
+        };
+        let mut links = Vec::new();
+        for kind in LinkKind::ALL {
+            let targets = match fm_map.get(kind.yaml_key()) {
+                Some(v) => serde_yaml::from_value::<Refs>(v.clone())
+                    .map(Refs::into_vec)
+                    .unwrap_or_default(),
+                None => doc
+                    .field(kind.header_key())
+                    .map(parse_adr_refs)
+                    .unwrap_or_default(),
+            };
+            links.extend(targets.into_iter().map(|target| AdrLink { kind, target }));
+        }
+        if deprecation_reason.is_none() {
+            deprecation_reason = doc.field("Deprecation-reason").map(str::to_string);
+        }
+        // `tags: [a, b]` or `tags: a, b` in front matter, else a `Tags:` line; the
+        // same goes for the people lists
+        let list = |yaml_key: &str, header_key: &str| match fm_map.get(yaml_key) {
+            Some(serde_yaml::Value::Sequence(items)) => {
+                // Unquoted `true` or `2024` items are still tags
+                let joined: Vec<String> = items
+                    .iter()
+                    .filter_map(|v| match v {
+                        serde_yaml::Value::String(s) => Some(s.clone()),
+                        serde_yaml::Value::Bool(b) => Some(b.to_string()),
+                        serde_yaml::Value::Number(n) => Some(n.to_string()),
+                        _ => None,
+                    })
+                    .collect();
+                parse_list(&joined.join(","))
+            }
+            Some(serde_yaml::Value::String(s)) => parse_list(s),
+            _ => doc.field(header_key).map(parse_list).unwrap_or_default(),
+        };
+        let tags = list("tags", "Tags");
+        let mut people = People::default();
+        for role in Role::ALL {
+            *people.get_mut(role) = list(role.yaml_key(), role.header_key());
+        }
 
         if title.is_empty() {
             title = self
@@ -115,13 +176,28 @@ impl FsAdrRepository {
             date = Local::now().format("%Y-%m-%d").to_string();
         }
 
+        // Status defaults to Accepted when missing
+        let status = status.unwrap_or_else(|| "Accepted".to_string());
+        let history = doc.history();
+        let created = history
+            .first()
+            .map(|c| c.date.clone())
+            .unwrap_or_else(|| date.clone());
+        let decided = decision_date(&status, &date, &history);
         Ok(AdrMeta {
             number,
             title,
             status,
             date,
+            created,
+            decided,
+            history,
             supersedes,
             superseded_by,
+            deprecation_reason,
+            links,
+            tags,
+            people,
             path: path.to_path_buf(),
         })
     }
@@ -167,7 +243,7 @@ impl AdrRepository for FsAdrRepository {
         if !self.root.exists() {
             return Ok(res);
         }
-        let re = Regex::new(r"^\d{4}-.*\.(md|mdx)$")
+        let re = Regex::new(ADR_FILE_PATTERN)
             .map_err(|e| anyhow!("invalid ADR filename regex: {}", e))?;
         for entry in fs::read_dir(&self.root)
             .with_context(|| format!("Reading ADR directory at {}", self.root.display()))?
@@ -201,6 +277,32 @@ impl AdrRepository for FsAdrRepository {
         f.write_all(content.as_bytes())?;
         Ok(())
     }
+
+    fn list_files(&self) -> Result<Vec<PathBuf>> {
+        let mut res = Vec::new();
+        if !self.root.exists() {
+            return Ok(res);
+        }
+        for entry in fs::read_dir(&self.root)
+            .with_context(|| format!("Reading ADR directory at {}", self.root.display()))?
+        {
+            let path = entry?.path();
+            if path.is_file() {
+                res.push(path);
+            }
+        }
+        res.sort();
+        Ok(res)
+    }
+
+    fn remove_file(&self, path: &Path) -> Result<()> {
+        fs::remove_file(path).with_context(|| format!("Removing {}", path.display()))
+    }
+
+    fn modified(&self, path: &Path) -> Option<NaiveDate> {
+        let modified = fs::metadata(path).ok()?.modified().ok()?;
+        Some(chrono::DateTime::<Local>::from(modified).date_naive())
+    }
 }
 
 #[cfg(test)]
@@ -259,10 +361,100 @@ mod tests {
         assert_eq!(a.title, "Overridden Title");
         assert_eq!(a.date, today);
         assert_eq!(a.status, "Proposed");
-        assert_eq!(a.supersedes, Some(2));
+        assert_eq!(a.supersedes, vec![2]);
         assert_eq!(a.superseded_by, Some(11));
     }
 
+    #[test]
+    fn test_parse_linked_supersedes() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        let p = root.join("0002-linked.md");
+        std::fs::write(
+            &p,
+            "# ADR 0002: Linked\n\nDate: 2024-01-01\nStatus: Proposed\nSupersedes: [0001](0001-old.md)\n",
+        )
+        .unwrap();
+        let repo = FsAdrRepository::new(root);
+        let list = repo.list().unwrap();
+        assert_eq!(list[0].supersedes, vec![1]);
+    }
+
+    #[test]
+    fn test_parse_multiple_supersedes() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(
+            root.join("0004-merged.md"),
+            "# ADR 0004: Merged\n\nDate: 2024-01-01\nStatus: Proposed\nSupersedes: [0001](0001-a.md), 0002, [0003](0003-c.md)\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("0005-front.md"),
+            "---\ntitle: Front\ndate: 2024-01-01\nstatus: Proposed\nsupersedes: [2, 3]\n---\n",
+        )
+        .unwrap();
+        let repo = FsAdrRepository::new(root);
+        let list = repo.list().unwrap();
+        assert_eq!(list[0].supersedes, vec![1, 2, 3]);
+        assert_eq!(list[1].supersedes, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_parse_tags_from_header_and_front_matter() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(
+            root.join("0001-a.md"),
+            "# ADR 0001: A\n\nDate: 2024-01-01\nStatus: Proposed\nTags: database, security\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("0002-b.md"),
+            "---\nstatus: Proposed\ndate: 2024-01-01\ntags: [api, \"2024\"]\n---\n\n# B\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("0003-c.md"),
+            "---\nstatus: Proposed\ndate: 2024-01-01\ntags: api, storage\n---\n\n# C\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("0004-d.md"),
+            "---\nstatus: Proposed\ndate: 2024-01-01\ntags: [true, 2024]\n---\n\n# D\n",
+        )
+        .unwrap();
+        let repo = FsAdrRepository::new(root);
+        let list = repo.list().unwrap();
+        assert_eq!(list[0].tags, vec!["database", "security"]);
+        assert_eq!(list[1].tags, vec!["api", "2024"]);
+        assert_eq!(list[2].tags, vec!["api", "storage"]);
+        assert_eq!(list[3].tags, vec!["true", "2024"]);
+    }
+
+    #[test]
+    fn test_parse_people_from_header_and_front_matter() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(
+            root.join("0001-a.md"),
+            "# ADR 0001: A\n\nDate: 2024-01-01\nStatus: Accepted\nAuthors: Ada\nDeciders: Ada, Grace\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("0002-b.md"),
+            "---\nstatus: Accepted\ndate: 2024-01-01\ndeciders: [Grace]\nconsulted:\n- Ops\n- Security\ninformed: []\n---\n\n# B\n",
+        )
+        .unwrap();
+        let repo = FsAdrRepository::new(root);
+        let list = repo.list().unwrap();
+        assert_eq!(list[0].people.authors, vec!["Ada"]);
+        assert_eq!(list[0].people.deciders, vec!["Ada", "Grace"]);
+        assert_eq!(list[1].people.deciders, vec!["Grace"]);
+        assert_eq!(list[1].people.consulted, vec!["Ops", "Security"]);
+        assert!(list[1].people.informed.is_empty());
+    }
+
     #[test]
     fn test_untitled_when_empty_slug() {
         let dir = tempdir().unwrap();
@@ -298,10 +490,56 @@ superseded_by: 5\n\
         assert_eq!(a.title, "Front Matter Title");
         assert_eq!(a.date, "2025-01-02");
         assert_eq!(a.status, "Proposed");
-        assert_eq!(a.supersedes, Some(3));
+        assert_eq!(a.supersedes, vec![3]);
         assert_eq!(a.superseded_by, Some(5));
     }
 
+    #[test]
+    fn test_parse_madr_front_matter_and_heading() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        let p = root.join("0004-use-madr.md");
+        let content = "---\n\
+status: Superseded by 0006\n\
+date: 2024-03-04\n\
+deciders: [alice, bob]\n\
+supersedes: 2\n\
+superseded_by: 6\n\
+---\n\n# Use MADR\n\n## Context and Problem Statement\n\nWe need a format.\n";
+        std::fs::write(&p, content).unwrap();
+        let repo = FsAdrRepository::new(root);
+        let a = &repo.list().unwrap()[0];
+        assert_eq!(a.number, 4);
+        assert_eq!(a.title, "Use MADR");
+        assert_eq!(a.status, "Superseded by 0006");
+        assert_eq!(a.date, "2024-03-04");
+        assert_eq!(a.supersedes, vec![2]);
+        assert_eq!(a.superseded_by, Some(6));
+    }
+
+    #[test]
+    fn test_body_status_examples_do_not_override_metadata() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(
+            root.join("0001-examples.md"),
+            "# ADR 0001: Examples\n\nDate: 2024-01-01\nStatus: Proposed\n\n## Decision\n\n```\nStatus: Accepted\nSupersedes: 0009\n```\n\n> Status: Rejected\n\n| Key | Value |\n|---|---|\n| Status: | Deprecated |\n\nStatus: Withdrawn\nDate: 1999-01-01\n",
+        )
+        .unwrap();
+        // No metadata block at all: a body line must not be taken as the status
+        std::fs::write(
+            root.join("0002-no-meta.md"),
+            "# ADR 0002: No Meta\n\nIntro.\n\nStatus: Rejected\n",
+        )
+        .unwrap();
+        let repo = FsAdrRepository::new(root);
+        let list = repo.list().unwrap();
+        assert_eq!(list[0].status, "Proposed");
+        assert_eq!(list[0].date, "2024-01-01");
+        assert!(list[0].supersedes.is_empty());
+        assert_eq!(list[1].status, "Accepted");
+    }
+
     #[test]
     fn test_list_includes_mdx_and_md() {
         let dir = tempdir().unwrap();
@@ -1,15 +1,24 @@
 use anyhow::Result;
+use chrono::NaiveDate;
 use std::path::{Path, PathBuf};
 
 use crate::domain::AdrMeta;
 
 pub mod fs;
 
+/// Filenames the repository reads as ADRs, e.g. `0003-use-postgres.md`.
+pub const ADR_FILE_PATTERN: &str = r"^\d{4}-.*\.(md|mdx)$";
+
 pub trait AdrRepository {
     fn adr_dir(&self) -> &Path;
     fn list(&self) -> Result<Vec<AdrMeta>>;
     fn read_string(&self, path: &Path) -> Result<String>;
     fn write_string(&self, path: &Path, content: &str) -> Result<()>;
+    /// Files directly inside the ADR directory, ADRs or not.
+    fn list_files(&self) -> Result<Vec<PathBuf>>;
+    fn remove_file(&self, path: &Path) -> Result<()>;
+    /// Local date the file was last modified, if known.
+    fn modified(&self, path: &Path) -> Option<NaiveDate>;
 }
 
 pub fn idx_path(dir: &Path, index_name: &str) -> PathBuf {
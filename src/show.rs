@@ -0,0 +1,433 @@
+use anyhow::{anyhow, Result};
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use std::collections::HashMap;
+use std::ffi::OsStr;
+
+use crate::document::AdrDocument;
+use crate::domain::{link_targets, AdrMeta, LinkKind, Role};
+use crate::repository::AdrRepository;
+
+const BOLD: &str = "1";
+const ITALIC: &str = "3";
+const UNDERLINE: &str = "4";
+const DIM: &str = "2";
+const CYAN: &str = "36";
+
+/// Renders an ADR for the terminal: a header with its metadata and related ADRs
+/// (by number and title), then the body. `color` turns on ANSI styling.
+pub fn show_adr<R: AdrRepository>(repo: &R, meta: &AdrMeta, color: bool) -> Result<String> {
+    let adrs = repo.list()?;
+    let doc = AdrDocument::parse(&repo.read_string(&meta.path)?);
+    let mut out = render_header(meta, &adrs, color);
+    out.push_str(&render_markdown(&doc.body(), &adrs, color));
+    Ok(out)
+}
+
+/// Renders the body of one section (matched case-insensitively) for the terminal.
+pub fn show_section<R: AdrRepository>(
+    repo: &R,
+    meta: &AdrMeta,
+    name: &str,
+    color: bool,
+) -> Result<String> {
+    let adrs = repo.list()?;
+    let (_, content) = section_content(repo, meta, name)?;
+    Ok(render_markdown(&content, &adrs, color))
+}
+
+/// Heading and Markdown below it of one section, matched case-insensitively. The
+/// error lists the sections the ADR has.
+pub fn section_content<R: AdrRepository>(
+    repo: &R,
+    meta: &AdrMeta,
+    name: &str,
+) -> Result<(String, String)> {
+    let doc = AdrDocument::parse(&repo.read_string(&meta.path)?);
+    match doc.section(name.trim()) {
+        Some(section) => Ok((
+            section.name().unwrap_or(name.trim()).to_string(),
+            section.content().trim().to_string() + "\n",
+        )),
+        None => {
+            let names: Vec<&str> = doc.sections().iter().filter_map(|s| s.name()).collect();
+            Err(anyhow!(
+                "ADR {:04} has no section '{}' (sections: {})",
+                meta.number,
+                name.trim(),
+                if names.is_empty() {
+                    "none".to_string()
+                } else {
+                    names.join(", ")
+                }
+            ))
+        }
+    }
+}
+
+fn render_header(meta: &AdrMeta, adrs: &[AdrMeta], color: bool) -> String {
+    let style = |code: &str, text: &str| styled(color, code, text);
+    let describe = |n: u32| match adrs.iter().find(|a| a.number == n) {
+        Some(a) => format!("{:04} {}", n, a.title),
+        None => format!("{:04}", n),
+    };
+    let title = format!("{:04}: {}", meta.number, meta.title);
+    let mut out = format!("{}\n", style(BOLD, &title));
+    out.push_str(&format!("{}\n", "=".repeat(title.chars().count())));
+
+    let mut lines: Vec<(String, String)> = vec![
+        ("Status".into(), meta.status.clone()),
+        ("Date".into(), meta.date.clone()),
+    ];
+    if let Some(reason) = &meta.deprecation_reason {
+        lines.push(("Deprecation reason".into(), reason.clone()));
+    }
+    if !meta.supersedes.is_empty() {
+        let items: Vec<String> = meta.supersedes.iter().map(|n| describe(*n)).collect();
+        lines.push(("Supersedes".into(), items.join(", ")));
+    }
+    if let Some(n) = meta.superseded_by {
+        lines.push(("Superseded by".into(), describe(n)));
+    }
+    for kind in LinkKind::ALL {
+        let targets = link_targets(&meta.links, kind);
+        if !targets.is_empty() {
+            let items: Vec<String> = targets.iter().map(|n| describe(*n)).collect();
+            lines.push((kind.header_key().into(), items.join(", ")));
+        }
+    }
+    if !meta.tags.is_empty() {
+        lines.push(("Tags".into(), meta.tags.join(", ")));
+    }
+    for role in Role::ALL {
+        let names = meta.people.get(role);
+        if !names.is_empty() {
+            lines.push((role.header_key().into(), names.join(", ")));
+        }
+    }
+    for (key, value) in lines {
+        out.push_str(&format!("{} {}\n", style(DIM, &format!("{}:", key)), value));
+    }
+    out.push('\n');
+    out
+}
+
+fn styled(color: bool, code: &str, text: &str) -> String {
+    if color {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Writes rendered text, prefixing every new line with the active indentation
+/// (list items, code blocks, block quotes) and tracking ANSI styles.
+struct Writer {
+    out: String,
+    color: bool,
+    styles: Vec<&'static str>,
+    prefixes: Vec<String>,
+    at_line_start: bool,
+}
+
+impl Writer {
+    fn text(&mut self, text: &str) {
+        for (i, part) in text.split('\n').enumerate() {
+            if i > 0 {
+                self.newline();
+            }
+            if part.is_empty() {
+                continue;
+            }
+            if self.at_line_start {
+                let prefix = self.prefixes.concat();
+                self.raw(&prefix);
+                self.at_line_start = false;
+            }
+            self.raw(part);
+        }
+    }
+
+    fn raw(&mut self, text: &str) {
+        self.out.push_str(text);
+    }
+
+    fn newline(&mut self) {
+        self.out.push('\n');
+        self.at_line_start = true;
+    }
+
+    /// Ends the current line, if any, and adds a blank line unless there is one.
+    fn block_end(&mut self) {
+        if !self.at_line_start {
+            self.newline();
+        }
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") && self.prefixes.is_empty() {
+            self.newline();
+        }
+    }
+
+    fn push_style(&mut self, code: &'static str) {
+        self.styles.push(code);
+        if self.color {
+            self.out.push_str(&format!("\x1b[{}m", code));
+        }
+    }
+
+    fn pop_style(&mut self) {
+        self.styles.pop();
+        if self.color {
+            self.out.push_str("\x1b[0m");
+            for code in &self.styles {
+                self.out.push_str(&format!("\x1b[{}m", code));
+            }
+        }
+    }
+}
+
+/// Renders Markdown for the terminal: styled headings (underlined with `=`/`-`
+/// for levels 1 and 2), emphasis, bullet and numbered lists, indented code blocks
+/// and quotes. Links show their target; links to ADR files in `adrs` show the
+/// ADR number and title instead.
+pub fn render_markdown(markdown: &str, adrs: &[AdrMeta], color: bool) -> String {
+    let mut files: HashMap<&str, &AdrMeta> = HashMap::new();
+    for a in adrs {
+        if let Some(fname) = a.path.file_name().and_then(OsStr::to_str) {
+            files.insert(fname, a);
+        }
+    }
+    let mut w = Writer {
+        out: String::new(),
+        color,
+        styles: Vec::new(),
+        prefixes: Vec::new(),
+        at_line_start: true,
+    };
+    // Per open list: the next item number, `None` for bullet lists
+    let mut lists: Vec<Option<u64>> = Vec::new();
+    let mut heading: Option<(HeadingLevel, usize)> = None;
+    let mut link: Option<(String, usize)> = None;
+    let mut in_code_block = false;
+
+    for event in Parser::new_ext(markdown, Options::ENABLE_TABLES | Options::ENABLE_TASKLISTS) {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                w.block_end();
+                w.push_style(BOLD);
+                if level == HeadingLevel::H2 {
+                    w.push_style(CYAN);
+                }
+                heading = Some((level, w.out.len()));
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some((level, start)) = heading.take() {
+                    let width = strip_ansi(&w.out[start..]).chars().count();
+                    if level == HeadingLevel::H2 {
+                        w.pop_style();
+                    }
+                    w.pop_style();
+                    match level {
+                        HeadingLevel::H1 => {
+                            w.newline();
+                            w.text(&"=".repeat(width));
+                        }
+                        HeadingLevel::H2 => {
+                            w.newline();
+                            w.text(&"-".repeat(width));
+                        }
+                        _ => {}
+                    }
+                }
+                w.block_end();
+            }
+            Event::Start(Tag::Paragraph) => {}
+            Event::End(TagEnd::Paragraph) => w.block_end(),
+            Event::Start(Tag::Emphasis) => w.push_style(ITALIC),
+            Event::Start(Tag::Strong) => w.push_style(BOLD),
+            Event::End(TagEnd::Emphasis) | Event::End(TagEnd::Strong) => w.pop_style(),
+            Event::Start(Tag::Strikethrough) => w.push_style("9"),
+            Event::End(TagEnd::Strikethrough) => w.pop_style(),
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                w.push_style(UNDERLINE);
+                link = Some((dest_url.to_string(), w.out.len()));
+            }
+            Event::End(TagEnd::Link) => {
+                w.pop_style();
+                if let Some((dest, start)) = link.take() {
+                    let text = strip_ansi(&w.out[start..]);
+                    let file = dest.trim_start_matches("./");
+                    if let Some(a) = files.get(file) {
+                        let target = format!("{:04}: {}", a.number, a.title);
+                        if text != target {
+                            w.text(&format!(" ({})", styled(color, DIM, &target)));
+                        }
+                    } else if text != dest {
+                        w.text(&format!(" ({})", styled(color, DIM, &dest)));
+                    }
+                }
+            }
+            Event::Start(Tag::List(start)) => {
+                if lists.is_empty() {
+                    w.block_end();
+                } else if !w.at_line_start {
+                    w.newline();
+                }
+                lists.push(start);
+            }
+            Event::End(TagEnd::List(_)) => {
+                lists.pop();
+                if lists.is_empty() {
+                    w.block_end();
+                }
+            }
+            Event::Start(Tag::Item) => {
+                if !w.at_line_start {
+                    w.newline();
+                }
+                let marker = match lists.last_mut() {
+                    Some(Some(n)) => {
+                        *n += 1;
+                        format!("{}. ", *n - 1)
+                    }
+                    _ => "• ".to_string(),
+                };
+                w.text(&marker);
+                w.prefixes.push(" ".repeat(marker.chars().count()));
+            }
+            Event::End(TagEnd::Item) => {
+                w.prefixes.pop();
+                if !w.at_line_start {
+                    w.newline();
+                }
+            }
+            Event::Start(Tag::CodeBlock(_)) => {
+                w.block_end();
+                w.prefixes.push("    ".to_string());
+                w.push_style(CYAN);
+                in_code_block = true;
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                in_code_block = false;
+                w.pop_style();
+                w.prefixes.pop();
+                w.block_end();
+            }
+            Event::Start(Tag::BlockQuote(_)) => {
+                w.block_end();
+                w.prefixes.push("│ ".to_string());
+            }
+            Event::End(TagEnd::BlockQuote(_)) => {
+                w.prefixes.pop();
+                w.block_end();
+            }
+            Event::Start(Tag::TableRow) | Event::Start(Tag::TableHead) => {}
+            Event::End(TagEnd::TableCell) => w.text(" | "),
+            Event::End(TagEnd::TableRow) | Event::End(TagEnd::TableHead) => w.newline(),
+            Event::End(TagEnd::Table) => w.block_end(),
+            Event::Text(t) => {
+                // Code block text ends with a newline that would leave an empty prefixed line
+                let t = if in_code_block {
+                    t.strip_suffix('\n').unwrap_or(&t).to_string() + "\n"
+                } else {
+                    t.to_string()
+                };
+                w.text(&t);
+            }
+            Event::Code(t) => {
+                if color {
+                    w.push_style(CYAN);
+                    w.text(&t);
+                    w.pop_style();
+                } else {
+                    w.text(&format!("`{}`", t));
+                }
+            }
+            Event::SoftBreak | Event::HardBreak => w.newline(),
+            Event::Rule => {
+                w.block_end();
+                w.text(&"─".repeat(40));
+                w.block_end();
+            }
+            Event::TaskListMarker(done) => w.text(if done { "[x] " } else { "[ ] " }),
+            Event::Html(t) | Event::InlineHtml(t) => w.text(&t),
+            _ => {}
+        }
+    }
+    let out = w.out.trim_end().to_string();
+    if out.is_empty() {
+        out
+    } else {
+        out + "\n"
+    }
+}
+
+fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            for c in chars.by_ref() {
+                if c == 'm' {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::People;
+    use std::path::PathBuf;
+
+    fn meta(number: u32, title: &str) -> AdrMeta {
+        AdrMeta {
+            number,
+            title: title.to_string(),
+            status: "Accepted".to_string(),
+            date: "2024-01-01".to_string(),
+            created: "2024-01-01".to_string(),
+            decided: None,
+            history: Vec::new(),
+            supersedes: Vec::new(),
+            superseded_by: None,
+            deprecation_reason: None,
+            links: Vec::new(),
+            tags: Vec::new(),
+            people: People::default(),
+            path: PathBuf::from(format!("{:04}-x.md", number)),
+        }
+    }
+
+    #[test]
+    fn renders_markdown_as_plain_text() {
+        let adrs = vec![meta(3, "Use MySQL")];
+        let md = "## Decision\n\nWe use *Postgres*, see [0003](0003-x.md) and [docs](https://example.com).\n\n- one\n- two\n  1. nested\n\n```\nSELECT 1;\n```\n\n> Quoted\n";
+        assert_eq!(
+            render_markdown(md, &adrs, false),
+            "Decision\n--------\n\nWe use Postgres, see 0003 (0003: Use MySQL) and docs (https://example.com).\n\n• one\n• two\n  1. nested\n\n    SELECT 1;\n\n│ Quoted\n"
+        );
+    }
+
+    #[test]
+    fn color_wraps_styles_and_keeps_nesting() {
+        let out = render_markdown("**bold _both_**", &[], true);
+        assert_eq!(out, "\x1b[1mbold \x1b[3mboth\x1b[0m\x1b[1m\x1b[0m\n");
+        assert_eq!(strip_ansi(&out), "bold both\n");
+    }
+
+    #[test]
+    fn header_lists_related_adrs_by_title() {
+        let mut m = meta(4, "Use Postgres");
+        m.supersedes = vec![3];
+        m.tags = vec!["database".into()];
+        let out = render_header(&m, &[meta(3, "Use MySQL")], false);
+        assert_eq!(
+            out,
+            "0004: Use Postgres\n==================\nStatus: Accepted\nDate: 2024-01-01\nSupersedes: 0003 Use MySQL\nTags: database\n\n"
+        );
+    }
+}
@@ -0,0 +1,301 @@
+use anyhow::Result;
+use pulldown_cmark::{html, Event, Options, Parser};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::document::AdrDocument;
+use crate::domain::{status_class, AdrMeta};
+use crate::repository::AdrRepository;
+
+const STYLE_CSS: &str = "body{font-family:system-ui,sans-serif;max-width:56rem;margin:2rem auto;padding:0 1rem;color:#212529;line-height:1.5}\n\
+a{color:#0b5ed7}\n\
+nav.adr-nav{border:1px solid #dee2e6;border-radius:.25rem;padding:.5rem 1rem;margin:1rem 0;background:#f8f9fa}\n\
+table{border-collapse:collapse;width:100%}\n\
+th,td{text-align:left;padding:.25rem .5rem;border-bottom:1px solid #dee2e6}\n\
+.status{display:inline-block;padding:0 .4rem;border-radius:.25rem;font-size:.85em;background:#e9ecef}\n\
+.status-accepted{background:#d4edda}\n\
+.status-proposed{background:#fff3cd}\n\
+.status-rejected{background:#f8d7da}\n\
+.status-superseded{background:#e2e3e5}\n\
+.status-deprecated{background:#ede7f6}\n\
+.filters{display:flex;gap:1rem;margin:1rem 0}\n\
+pre{background:#f8f9fa;padding:.75rem;overflow-x:auto}\n";
+
+const SEARCH_JS: &str = r##"(function () {
+  var rows = Array.prototype.slice.call(document.querySelectorAll("#adrs tbody tr"));
+  var status = document.getElementById("status-filter");
+  var query = document.getElementById("search");
+  var docs = {};
+  fetch("search-index.json")
+    .then(function (r) { return r.json(); })
+    .then(function (items) {
+      items.forEach(function (d) { docs[d.url] = (d.title + " " + d.text).toLowerCase(); });
+      apply();
+    })
+    .catch(function () {});
+  function apply() {
+    var s = status.value;
+    var q = query.value.trim().toLowerCase();
+    rows.forEach(function (row) {
+      var okStatus = !s || row.dataset.status === s;
+      var text = docs[row.dataset.url] || row.textContent.toLowerCase();
+      var okQuery = !q || text.indexOf(q) !== -1;
+      row.style.display = okStatus && okQuery ? "" : "none";
+    });
+  }
+  status.addEventListener("change", apply);
+  query.addEventListener("input", apply);
+})();
+"##;
+
+/// One entry of `search-index.json`, consumed by the client-side search.
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchEntry {
+    pub number: u32,
+    pub title: String,
+    pub status: String,
+    pub date: String,
+    pub url: String,
+    pub text: String,
+}
+
+/// Renders every ADR to a static HTML site under `out`: one page per ADR, an
+/// `index.html` with status filter and search, `search-index.json` and assets.
+/// Returns the paths written.
+pub fn build_site<R: AdrRepository>(repo: &R, cfg: &Config, out: &Path) -> Result<Vec<PathBuf>> {
+    let adrs = repo.list()?;
+    let by_number: HashMap<u32, &AdrMeta> = adrs.iter().map(|a| (a.number, a)).collect();
+
+    // Source filename -> page filename, so cross-links keep working in the site
+    let mut pages: HashMap<String, String> = HashMap::new();
+    for a in &adrs {
+        if let Some(fname) = a.path.file_name().and_then(OsStr::to_str) {
+            pages.insert(fname.to_string(), page_name(a));
+        }
+    }
+    pages.insert(cfg.index_name.clone(), "index.html".to_string());
+
+    let mut written = Vec::new();
+    let mut search = Vec::with_capacity(adrs.len());
+    for a in &adrs {
+        // The page header already shows the title, so only the metadata and body are kept
+        let doc = AdrDocument::parse(&repo.read_string(&a.path)?);
+        let body = format!("{}{}", doc.meta_text(), doc.body());
+        let markdown = rewrite_links(&body, &pages);
+        let html_body = markdown_to_html(&markdown);
+
+        let mut nav = String::new();
+        let old: Vec<String> = a
+            .supersedes
+            .iter()
+            .filter_map(|n| by_number.get(n))
+            .map(|o| adr_link(o))
+            .collect();
+        if !old.is_empty() {
+            nav.push_str(&format!("<p>Supersedes: {}</p>", old.join(", ")));
+        }
+        if let Some(new) = a.superseded_by.and_then(|n| by_number.get(&n)) {
+            nav.push_str(&format!("<p>Superseded by: {}</p>", adr_link(new)));
+        }
+        nav.push_str("<p><a href=\"index.html\">All decisions</a></p>");
+
+        let title = format!("{:04}: {}", a.number, a.title);
+        let content = format!(
+            "<header><h1>{}</h1><p><span class=\"status status-{}\">{}</span> {}</p></header>\n<nav class=\"adr-nav\">{}</nav>\n<main>\n{}</main>\n",
+            escape_html(&title),
+            status_class(&a.status),
+            escape_html(&a.status),
+            escape_html(&a.date),
+            nav,
+            html_body
+        );
+        let path = out.join(page_name(a));
+        repo.write_string(&path, &page(&title, &content, ""))?;
+        written.push(path);
+
+        search.push(SearchEntry {
+            number: a.number,
+            title: a.title.clone(),
+            status: a.status.clone(),
+            date: a.date.clone(),
+            url: page_name(a),
+            text: plain_text(&markdown),
+        });
+    }
+
+    let index = out.join("index.html");
+    repo.write_string(&index, &render_index_page(&adrs))?;
+    written.push(index);
+
+    let search_path = out.join("search-index.json");
+    repo.write_string(&search_path, &serde_json::to_string(&search)?)?;
+    written.push(search_path);
+
+    for (name, content) in [("style.css", STYLE_CSS), ("search.js", SEARCH_JS)] {
+        let path = out.join(name);
+        repo.write_string(&path, content)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+fn page_name(a: &AdrMeta) -> String {
+    let stem = a
+        .path
+        .file_stem()
+        .and_then(OsStr::to_str)
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| format!("{:04}", a.number));
+    format!("{}.html", stem)
+}
+
+fn adr_link(a: &AdrMeta) -> String {
+    format!(
+        "<a href=\"{}\">{:04}: {}</a>",
+        page_name(a),
+        a.number,
+        escape_html(&a.title)
+    )
+}
+
+/// Rewrites Markdown link targets that name an ADR file (or the index) to the
+/// corresponding HTML page, e.g. `](0003-foo.md)` -> `](0003-foo.html)`.
+fn rewrite_links(markdown: &str, pages: &HashMap<String, String>) -> String {
+    let mut out = markdown.to_string();
+    for (src, dst) in pages {
+        out = out.replace(&format!("]({})", src), &format!("]({})", dst));
+        out = out.replace(&format!("](./{})", src), &format!("]({})", dst));
+    }
+    out
+}
+
+fn markdown_to_html(markdown: &str) -> String {
+    let parser = Parser::new_ext(markdown, Options::ENABLE_TABLES);
+    let mut out = String::new();
+    html::push_html(&mut out, parser);
+    out
+}
+
+fn plain_text(markdown: &str) -> String {
+    let mut words: Vec<String> = Vec::new();
+    for ev in Parser::new(markdown) {
+        if let Event::Text(t) | Event::Code(t) = ev {
+            words.push(t.to_string());
+        }
+    }
+    words.join(" ")
+}
+
+fn render_index_page(adrs: &[AdrMeta]) -> String {
+    let statuses: BTreeSet<&str> = adrs.iter().map(|a| status_class(&a.status)).collect();
+    let mut content = String::from("<header><h1>Architecture Decision Records</h1></header>\n");
+    content.push_str("<div class=\"filters\">\n<label>Status <select id=\"status-filter\"><option value=\"\">All</option>");
+    for s in &statuses {
+        content.push_str(&format!("<option value=\"{}\">{}</option>", s, s));
+    }
+    content.push_str(
+        "</select></label>\n<label>Search <input id=\"search\" type=\"search\"></label>\n</div>\n",
+    );
+    content.push_str("<table id=\"adrs\">\n<thead><tr><th>#</th><th>Title</th><th>Status</th><th>Date</th></tr></thead>\n<tbody>\n");
+    for a in adrs {
+        content.push_str(&format!(
+            "<tr data-status=\"{}\" data-url=\"{}\"><td>{:04}</td><td><a href=\"{}\">{}</a></td><td><span class=\"status status-{}\">{}</span></td><td>{}</td></tr>\n",
+            status_class(&a.status),
+            page_name(a),
+            a.number,
+            page_name(a),
+            escape_html(&a.title),
+            status_class(&a.status),
+            escape_html(&a.status),
+            escape_html(&a.date)
+        ));
+    }
+    content.push_str("</tbody>\n</table>\n");
+    page(
+        "Architecture Decision Records",
+        &content,
+        "<script src=\"search.js\"></script>\n",
+    )
+}
+
+fn page(title: &str, content: &str, scripts: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<link rel=\"stylesheet\" href=\"style.css\">\n</head>\n<body>\n{}{}</body>\n</html>\n",
+        escape_html(title),
+        content,
+        scripts
+    )
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{create_new_adr, mark_superseded};
+    use crate::domain::People;
+    use crate::repository::fs::FsAdrRepository;
+    use tempfile::tempdir;
+
+    #[test]
+    fn builds_pages_index_and_search() {
+        let dir = tempdir().unwrap();
+        let adr_dir = dir.path().join("adrs");
+        let out = dir.path().join("public");
+        let repo = FsAdrRepository::new(&adr_dir);
+        let cfg = Config {
+            adr_dir: adr_dir.clone(),
+            ..Config::default()
+        };
+        let old = create_new_adr(&repo, &cfg, "Choose <X>", &[], &[], &People::default()).unwrap();
+        let new_meta = create_new_adr(
+            &repo,
+            &cfg,
+            "Choose Y",
+            &[old.number],
+            &[],
+            &People::default(),
+        )
+        .unwrap();
+        mark_superseded(&repo, &cfg, old.number, new_meta.number).unwrap();
+
+        let written = build_site(&repo, &cfg, &out).unwrap();
+        assert_eq!(written.len(), 6);
+
+        let p1 = std::fs::read_to_string(out.join("0001-choose-x.html")).unwrap();
+        assert!(p1.contains("<h1>0001: Choose &lt;X&gt;</h1>"));
+        assert!(p1.contains("Superseded by: <a href=\"0002-choose-y.html\">"));
+        assert!(p1.contains("<h2>Context</h2>"));
+
+        let p2 = std::fs::read_to_string(out.join("0002-choose-y.html")).unwrap();
+        // Supersedes line in the body links to the HTML page
+        assert!(p2.contains("<a href=\"0001-choose-x.html\">0001</a>"));
+        assert!(!p2.contains(".md\""));
+
+        let idx = std::fs::read_to_string(out.join("index.html")).unwrap();
+        assert!(idx.contains("<option value=\"superseded\">"));
+        assert!(idx.contains("data-status=\"proposed\" data-url=\"0002-choose-y.html\""));
+
+        let search: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(out.join("search-index.json")).unwrap())
+                .unwrap();
+        assert_eq!(search[1]["url"], "0002-choose-y.html");
+        assert!(search[0]["text"].as_str().unwrap().contains("Context"));
+    }
+
+    #[test]
+    fn rewrite_links_only_touches_known_files() {
+        let mut pages = HashMap::new();
+        pages.insert("0001-a.mdx".to_string(), "0001-a.html".to_string());
+        let out = rewrite_links("[a](0001-a.mdx) [b](other.md)", &pages);
+        assert_eq!(out, "[a](0001-a.html) [b](other.md)");
+    }
+}
@@ -23,10 +23,135 @@ pub fn escape_yaml(input: &str) -> String {
     }
 }
 
+/// `value` as a YAML scalar that reads back as the same string: plain when that
+/// is safe, else double-quoted. Unlike [`escape_yaml`], this also quotes
+/// comments (`#`), flow indicators such as `[` or `,`, and values YAML reads as
+/// something else, e.g. `true`, `null` or `2024`, so it fits both block values
+/// and flow sequence entries.
+pub fn yaml_scalar(value: &str) -> String {
+    let plain = value.trim() == value
+        && !value.starts_with(['-', '?', '!', '%', '@', '`', '\'', '"', '|', '>'])
+        && !value.contains(|c: char| ",[]{}#&*:".contains(c) || c.is_control())
+        && matches!(
+            serde_yaml::from_str(value),
+            Ok(serde_yaml::Value::String(s)) if s == value
+        );
+    if plain {
+        value.to_string()
+    } else {
+        serde_json::Value::from(value).to_string()
+    }
+}
+
+/// Returns the line range of a top-level `key:` entry in a YAML block, including
+/// indented or `- ` continuation lines.
+fn field_range(lines: &[&str], key: &str) -> Option<(usize, usize)> {
+    let prefix = format!("{}:", key);
+    let start = lines.iter().position(|l| l.starts_with(&prefix))?;
+    let mut end = start + 1;
+    while end < lines.len()
+        && (lines[end].starts_with(' ')
+            || lines[end].starts_with('\t')
+            || lines[end].starts_with("- "))
+    {
+        end += 1;
+    }
+    Some((start, end))
+}
+
+/// True when the YAML block has a top-level `key:` entry.
+pub fn has_yaml_field(block: &str, key: &str) -> bool {
+    let lines: Vec<&str> = block.lines().collect();
+    field_range(&lines, key).is_some()
+}
+
+/// Sets a top-level `key: value` entry in a YAML block, replacing the existing entry
+/// (and its continuation lines) in place or appending it. `value` must already be a
+/// valid YAML scalar, e.g. passed through `escape_yaml`.
+pub fn set_yaml_field(block: &str, key: &str, value: &str) -> String {
+    set_yaml_entry(block, key, &format!("{}: {}", key, value))
+}
+
+/// Replaces the top-level `key` entry of a YAML block with `entry`, the complete
+/// YAML text of the entry (possibly spanning several lines), or appends it.
+pub fn set_yaml_entry(block: &str, key: &str, entry: &str) -> String {
+    let mut lines: Vec<&str> = block.lines().collect();
+    let new_lines = entry.lines();
+    match field_range(&lines, key) {
+        Some((start, end)) => {
+            lines.splice(start..end, new_lines);
+        }
+        None => lines.extend(new_lines),
+    }
+    lines.join("\n")
+}
+
+/// Removes the top-level `key` entry (and its continuation lines) from a YAML block.
+pub fn remove_yaml_field(block: &str, key: &str) -> String {
+    let mut lines: Vec<&str> = block.lines().collect();
+    if let Some((start, end)) = field_range(&lines, key) {
+        lines.drain(start..end);
+    }
+    lines.join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn yaml_scalar_round_trips() {
+        for value in [
+            "Replaced per ticket #123",
+            "[legacy] moved to vendor",
+            "- not a list",
+            "#arch-team",
+            "true",
+            "null",
+            "~",
+            "2024",
+            "{x}",
+            "key: value",
+            "say \"hi\"",
+            "",
+            " padded ",
+            "Bob's Book",
+        ] {
+            let yaml = format!("k: {}\n", yaml_scalar(value));
+            let parsed: std::collections::BTreeMap<String, String> =
+                serde_yaml::from_str(&yaml).unwrap_or_else(|e| panic!("{}: {}", yaml, e));
+            assert_eq!(parsed["k"], value, "{}", yaml);
+        }
+        assert_eq!(yaml_scalar("Moved to Kafka"), "Moved to Kafka");
+        assert_eq!(yaml_scalar("#ops"), "\"#ops\"");
+    }
+
+    #[test]
+    fn set_yaml_field_replaces_in_place_and_appends() {
+        let block = "status: Proposed\ndeciders:\n  - alice\n  - bob\ndate: 2024-01-01";
+        let out = set_yaml_field(block, "deciders", "[carol]");
+        assert_eq!(out, "status: Proposed\ndeciders: [carol]\ndate: 2024-01-01");
+        let out = set_yaml_field(&out, "superseded_by", "3");
+        assert!(out.ends_with("\nsuperseded_by: 3"));
+        assert!(has_yaml_field(&out, "status"));
+        assert!(!has_yaml_field(&out, "stat"));
+        let out = remove_yaml_field(block, "deciders");
+        assert_eq!(out, "status: Proposed\ndate: 2024-01-01");
+    }
+
+    #[test]
+    fn set_yaml_entry_writes_multi_line_entries() {
+        let block = "history: []\nstatus: Accepted";
+        let entry = "history:\n- date: 2024-01-01\n  status: Proposed\n";
+        let out = set_yaml_entry(block, "history", entry);
+        assert_eq!(
+            out,
+            "history:\n- date: 2024-01-01\n  status: Proposed\nstatus: Accepted"
+        );
+        let out = set_yaml_entry("status: Accepted", "history", entry);
+        assert!(out.ends_with("status: Accepted\nhistory:\n- date: 2024-01-01\n  status: Proposed"));
+    }
+
     #[test]
     fn quotes_when_contains_colon() {
         assert_eq!(escape_yaml("Title: With Colon"), "\"Title: With Colon\"");
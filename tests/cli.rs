@@ -10,10 +10,26 @@ fn read(path: impl Into<PathBuf>) -> String {
     fs::read_to_string(path.into()).expect("read file")
 }
 
+/// The radr binary isolated from whoever runs the tests: git's global and system
+/// config are hidden, so `new` does not pick up their user.name as author, and so
+/// are their radr user config and every `RADR_*` variable, `RADR_CONFIG` included.
+fn radr_cmd() -> assert_cmd::Command {
+    let mut cmd = assert_cmd::Command::cargo_bin("radr").unwrap();
+    cmd.env("GIT_CONFIG_GLOBAL", "/nonexistent/radr-test-gitconfig")
+        .env("GIT_CONFIG_NOSYSTEM", "1")
+        .env("XDG_CONFIG_HOME", "/nonexistent/radr-test-config");
+    for (var, _) in std::env::vars_os() {
+        if var.to_string_lossy().starts_with("RADR_") {
+            cmd.env_remove(var);
+        }
+    }
+    cmd
+}
+
 #[test]
 fn new_creates_proposed_and_index() {
     let tmp = tempfile::tempdir().unwrap();
-    let mut cmd = assert_cmd::Command::cargo_bin("radr").unwrap();
+    let mut cmd = radr_cmd();
     cmd.current_dir(tmp.path()).arg("new").arg("First ADR");
     cmd.assert().success();
 
@@ -36,16 +52,14 @@ fn accept_by_id_and_title_updates_status_and_date() {
     let today = chrono::Local::now().format("%Y-%m-%d").to_string();
 
     // new
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["new", "Choose DB"])
         .assert()
         .success();
 
     // accept by id
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["accept", "1"])
         .assert()
@@ -58,15 +72,13 @@ fn accept_by_id_and_title_updates_status_and_date() {
     assert!(c1.contains(&format!("Date: {}", today)));
 
     // new second and accept by title
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["new", "Use Queue"])
         .assert()
         .success();
 
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["accept", "Use Queue"])
         .assert()
@@ -82,16 +94,14 @@ fn supersede_marks_old_and_new_proposed_and_updates_index() {
     let tmp = tempfile::tempdir().unwrap();
 
     // create first
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["new", "Choose X"])
         .assert()
         .success();
 
     // supersede
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["supersede", "1", "Choose Y"])
         .assert()
@@ -125,8 +135,7 @@ fn list_outputs_lines_and_regenerates_index() {
 
     // create two
     for title in ["One", "Two"] {
-        assert_cmd::Command::cargo_bin("radr")
-            .unwrap()
+        radr_cmd()
             .current_dir(tmp.path())
             .args(["new", title])
             .assert()
@@ -134,7 +143,7 @@ fn list_outputs_lines_and_regenerates_index() {
     }
 
     // list
-    let mut cmd = assert_cmd::Command::cargo_bin("radr").unwrap();
+    let mut cmd = radr_cmd();
     cmd.current_dir(tmp.path()).arg("list");
     cmd.assert()
         .success()
@@ -154,8 +163,7 @@ fn config_flag_changes_adr_dir_and_index_name() {
     std::fs::write(&cfg, b"adr_dir: adrs\nindex_name: ADRS.md\n").unwrap();
 
     // Use --config to pick up YAML
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["--config", cfg.to_str().unwrap(), "new", "From Config"])
         .assert()
@@ -180,7 +188,7 @@ fn env_config_overrides_local_files() {
     let env_yaml = tmp.path().join("radr.yaml");
     std::fs::write(&env_yaml, b"adr_dir: env\nindex_name: ENV.md\n").unwrap();
 
-    let mut cmd = assert_cmd::Command::cargo_bin("radr").unwrap();
+    let mut cmd = radr_cmd();
     cmd.current_dir(tmp.path())
         .env("RADR_CONFIG", env_yaml)
         .args(["new", "From Env"])
@@ -205,8 +213,7 @@ fn mdx_new_creates_front_matter_and_index() {
     .unwrap();
 
     // create new
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["new", "MDX Test"])
         .assert()
@@ -242,16 +249,14 @@ fn mdx_accept_updates_front_matter() {
     )
     .unwrap();
 
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["new", "Accept Me"])
         .assert()
         .success();
 
     // accept should update classic fields after front matter
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["accept", "1"])
         .assert()
@@ -274,16 +279,14 @@ fn mdx_supersede_updates_front_matter_and_index() {
     .unwrap();
 
     // create first
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["new", "Choose X"])
         .assert()
         .success();
 
     // supersede
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["supersede", "1", "Choose Y"])
         .assert()
@@ -311,8 +314,7 @@ fn mdx_supersede_updates_front_matter_and_index() {
 #[test]
 fn accept_nonexistent_returns_error() {
     let tmp = tempfile::tempdir().unwrap();
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["accept", "9999"])
         .assert()
@@ -322,8 +324,7 @@ fn accept_nonexistent_returns_error() {
 #[test]
 fn supersede_nonexistent_returns_error() {
     let tmp = tempfile::tempdir().unwrap();
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["supersede", "9999", "Y"])
         .assert()
@@ -335,24 +336,21 @@ fn supersede_already_superseded_shows_message_and_fails() {
     let tmp = tempfile::tempdir().unwrap();
 
     // create first
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["new", "Choose X"])
         .assert()
         .success();
 
     // supersede once (1 -> 2)
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["supersede", "1", "Choose Y"])
         .assert()
         .success();
 
     // try to supersede ADR 1 again; should fail with a helpful message
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["supersede", "1", "Choose Z"])
         .assert()
@@ -370,24 +368,21 @@ fn supersede_already_superseded_force_allows_and_updates() {
     let tmp = tempfile::tempdir().unwrap();
 
     // create first
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["new", "Choose X"])
         .assert()
         .success();
 
     // supersede once (1 -> 2)
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["supersede", "1", "Choose Y"])
         .assert()
         .success();
 
     // supersede ADR 1 again with --force; should succeed and create ADR 3
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["supersede", "1", "Choose Z", "--force"])
         .assert()
@@ -411,16 +406,14 @@ fn reject_by_id_and_title_updates_status_and_date() {
     let today = chrono::Local::now().format("%Y-%m-%d").to_string();
 
     // new
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["new", "Discard Me"])
         .assert()
         .success();
 
     // reject by id
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["reject", "1"])
         .assert()
@@ -433,15 +426,13 @@ fn reject_by_id_and_title_updates_status_and_date() {
     assert!(c1.contains(&format!("Date: {}", today)));
 
     // new second and reject by title (case-insensitive)
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["new", "Reject This Too"])
         .assert()
         .success();
 
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["reject", "reject this too"])
         .assert()
@@ -472,8 +463,7 @@ fn template_via_config_is_applied() {
     )
     .unwrap();
 
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["new", "From Template"])
         .assert()
@@ -490,8 +480,7 @@ fn reformat_md_to_mdx_with_front_matter() {
     let tmp = tempfile::tempdir().unwrap();
 
     // Create ADR with defaults (md, no front matter)
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["new", "Switch Format"])
         .assert()
@@ -511,8 +500,7 @@ fn reformat_md_to_mdx_with_front_matter() {
     .unwrap();
 
     // Reformat
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["reformat", "1"])
         .assert()
@@ -538,8 +526,7 @@ fn reformat_mdx_to_md_without_front_matter() {
         b"adr_dir='docs/adr'\nformat='mdx'\nfront_matter=true\n",
     )
     .unwrap();
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["new", "Front First"])
         .assert()
@@ -557,8 +544,7 @@ fn reformat_mdx_to_md_without_front_matter() {
     )
     .unwrap();
 
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["reformat", "1"])
         .assert()
@@ -579,16 +565,14 @@ fn reformat_updates_incoming_links() {
     let tmp = tempfile::tempdir().unwrap();
 
     // Start classic md, no front matter
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["new", "Choose X"])
         .assert()
         .success();
 
     // Supersede 1 -> 2 (creates link to 0001-choose-x.md)
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["supersede", "1", "Choose Y"])
         .assert()
@@ -605,8 +589,7 @@ fn reformat_updates_incoming_links() {
     )
     .unwrap();
 
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["reformat", "1"])
         .assert()
@@ -622,14 +605,12 @@ fn reformat_all_converts_everything() {
     let tmp = tempfile::tempdir().unwrap();
 
     // Start with classic md
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["new", "First"])
         .assert()
         .success();
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["new", "Second"])
         .assert()
@@ -644,8 +625,7 @@ fn reformat_all_converts_everything() {
         b"adr_dir='docs/adr'\nformat='mdx'\nfront_matter=true\n",
     )
     .unwrap();
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["reformat", "--all"])
         .assert()
@@ -665,8 +645,7 @@ fn reformat_all_converts_everything() {
 fn reformat_idempotent_no_dup_meta() {
     let tmp = tempfile::tempdir().unwrap();
     // default config: md without front matter
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["new", "Idempotent"])
         .assert()
@@ -674,8 +653,7 @@ fn reformat_idempotent_no_dup_meta() {
 
     // Reformat twice, should not duplicate Date/Status
     for _ in 0..2 {
-        assert_cmd::Command::cargo_bin("radr")
-            .unwrap()
+        radr_cmd()
             .current_dir(tmp.path())
             .args(["reformat", "1"])
             .assert()
@@ -695,14 +673,12 @@ fn reformat_idempotent_no_dup_meta() {
 fn reformat_preserves_superseded_by_and_order() {
     let tmp = tempfile::tempdir().unwrap();
     // Create and supersede 1 -> 2 (classic md)
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["new", "Old One"])
         .assert()
         .success();
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["supersede", "1", "New One"])
         .assert()
@@ -714,8 +690,7 @@ fn reformat_preserves_superseded_by_and_order() {
         b"adr_dir='docs/adr'\nformat='mdx'\nfront_matter=true\n",
     )
     .unwrap();
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["reformat", "1"])
         .assert()
@@ -736,14 +711,12 @@ fn reformat_preserves_superseded_by_and_order() {
 fn reformat_updates_index_link_for_superseded_by() {
     let tmp = tempfile::tempdir().unwrap();
     // Create and supersede 1 -> 2
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["new", "Foo"])
         .assert()
         .success();
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["supersede", "1", "Bar"])
         .assert()
@@ -755,8 +728,7 @@ fn reformat_updates_index_link_for_superseded_by() {
         b"adr_dir='docs/adr'\nformat='mdx'\nfront_matter=true\n",
     )
     .unwrap();
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .args(["reformat", "2"])
         .assert()
@@ -771,10 +743,1479 @@ fn reformat_updates_index_link_for_superseded_by() {
 fn reformat_missing_id_fails_without_all() {
     let tmp = tempfile::tempdir().unwrap();
     // Just invoking reformat without id and without --all should fail
-    assert_cmd::Command::cargo_bin("radr")
-        .unwrap()
+    radr_cmd()
         .current_dir(tmp.path())
         .arg("reformat")
         .assert()
         .failure();
 }
+
+#[test]
+fn lint_passes_on_generated_adrs() {
+    let tmp = tempfile::tempdir().unwrap();
+    radr_cmd()
+        .current_dir(tmp.path())
+        .args(["new", "Choose X"])
+        .assert()
+        .success();
+    radr_cmd()
+        .current_dir(tmp.path())
+        .args(["supersede", "1", "Choose Y"])
+        .assert()
+        .success();
+
+    radr_cmd()
+        .current_dir(tmp.path())
+        .arg("lint")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0 error(s), 0 warning(s)"));
+}
+
+#[test]
+fn lint_reports_broken_adrs_and_fails() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = adr_dir(tmp.path());
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("0003-first.md"),
+        "# ADR 0003: First\n\nDate: 2024-01-01\nStatus: Accepted\nSupersedes: 0009\n",
+    )
+    .unwrap();
+    fs::write(dir.join("0003-second.md"), "# ADR 0003: Second\n\nBody\n").unwrap();
+
+    radr_cmd()
+        .current_dir(tmp.path())
+        .arg("lint")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "0003-first.md: error[duplicate-number]",
+        ))
+        .stdout(predicate::str::contains("error[dangling-link]"))
+        .stdout(predicate::str::contains(
+            "0003-second.md: error[missing-status]",
+        ))
+        .stdout(predicate::str::contains(
+            "0003-second.md: error[missing-date]",
+        ));
+}
+
+#[test]
+fn lint_strict_fails_on_warnings() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = adr_dir(tmp.path());
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("0001-old-name.md"),
+        "# ADR 0001: New Name\n\nDate: 2024-01-01\nStatus: Accepted\n",
+    )
+    .unwrap();
+
+    radr_cmd()
+        .current_dir(tmp.path())
+        .arg("lint")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("warning[title-mismatch]"));
+    radr_cmd()
+        .current_dir(tmp.path())
+        .args(["lint", "--strict"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn lint_fix_repairs_and_reports_per_file() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = adr_dir(tmp.path());
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("0001-old.md"),
+        "# ADR 0001: Old\n\nDate: 2024-01-01\nStatus: Accepted\n\n## Context\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("0002-new.md"),
+        "# ADR 0002: New\n\nDate: 2024-02-01\nStatus: Accepted\nSupersedes: 0001\n\n## Context\n",
+    )
+    .unwrap();
+
+    radr_cmd()
+        .current_dir(tmp.path())
+        .args(["lint", "--fix", "--strict"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Fixed 0001-old.md: added Superseded-by: 0002",
+        ))
+        .stdout(predicate::str::contains(
+            "Fixed 0002-new.md: linked Supersedes: [0001](0001-old.md)",
+        ))
+        .stdout(predicate::str::contains(
+            "Fixed index.md: regenerated index",
+        ))
+        .stdout(predicate::str::contains("0 error(s), 0 warning(s)"));
+
+    let old = read(dir.join("0001-old.md"));
+    assert!(old.contains("Status: Superseded by 0002\nSuperseded-by: 0002\n"));
+}
+
+#[test]
+fn output_json_for_new_and_list() {
+    let tmp = tempfile::tempdir().unwrap();
+    let out = radr_cmd()
+        .current_dir(tmp.path())
+        .args(["--output", "json", "new", "Choose X"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(v["number"], 1);
+    assert_eq!(v["title"], "Choose X");
+    assert_eq!(v["status"], "Proposed");
+    assert!(v["path"].as_str().unwrap().ends_with("0001-choose-x.md"));
+
+    radr_cmd()
+        .current_dir(tmp.path())
+        .args(["supersede", "1", "Choose Y", "--output", "json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"supersedes\": [\n    1\n  ]"));
+
+    let out = radr_cmd()
+        .current_dir(tmp.path())
+        .args(["list", "--output", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    let items = v.as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["superseded_by"], 2);
+    assert_eq!(items[1]["supersedes"], serde_json::json!([1]));
+}
+
+#[test]
+fn output_csv_and_yaml_for_list() {
+    let tmp = tempfile::tempdir().unwrap();
+    radr_cmd()
+        .current_dir(tmp.path())
+        .args(["new", "Hello, World"])
+        .assert()
+        .success();
+
+    radr_cmd()
+        .current_dir(tmp.path())
+        .args(["--output", "csv", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "number,title,status,date,created,decided,history,supersedes,superseded_by,deprecation_reason,links,tags,authors,deciders,consulted,informed,path\n1,\"Hello, World\",Proposed,",
+        ));
+
+    radr_cmd()
+        .current_dir(tmp.path())
+        .args(["--output", "yaml", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "- number: 1\n  title: Hello, World\n",
+        ));
+}
+
+#[test]
+fn output_json_errors_are_structured() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::create_dir_all(adr_dir(tmp.path())).unwrap();
+    let out = radr_cmd()
+        .current_dir(tmp.path())
+        .args(["--output", "json", "accept", "9999"])
+        .assert()
+        .failure()
+        .get_output()
+        .stderr
+        .clone();
+    let v: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert!(v["error"].as_str().unwrap().contains("ADR not found"));
+}
+
+#[test]
+fn graph_exports_chain_in_each_format() {
+    let tmp = tempfile::tempdir().unwrap();
+    for args in [
+        vec!["new", "One"],
+        vec!["supersede", "1", "Two"],
+        vec!["supersede", "2", "Three"],
+        vec!["new", "Unrelated"],
+    ] {
+        radr_cmd()
+            .current_dir(tmp.path())
+            .args(&args)
+            .assert()
+            .success();
+    }
+
+    radr_cmd()
+        .current_dir(tmp.path())
+        .arg("graph")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("flowchart TD\n"))
+        .stdout(predicate::str::contains("adr0003 -->|supersedes| adr0002"))
+        .stdout(predicate::str::contains("adr0004[\"0004: Unrelated\"]"));
+
+    radr_cmd()
+        .current_dir(tmp.path())
+        .args(["graph", "1", "--depth", "1", "--format", "dot"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("adr0002 -> adr0001"))
+        .stdout(predicate::str::contains("adr0003").not());
+
+    let out = tmp.path().join("graph.puml");
+    radr_cmd()
+        .current_dir(tmp.path())
+        .args([
+            "graph",
+            "--format",
+            "plantuml",
+            "--out",
+            out.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+    assert!(read(&out).starts_with("@startuml\n"));
+}
+
+#[test]
+fn index_graph_config_embeds_mermaid() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("radr.toml"), b"index_graph = true\n").unwrap();
+    radr_cmd()
+        .current_dir(tmp.path())
+        .args(["new", "One"])
+        .assert()
+        .success();
+
+    let idx = read(adr_dir(tmp.path()).join("index.md"));
+    assert!(idx.contains("```mermaid\nflowchart TD\n"));
+}
+
+#[test]
+fn site_generates_html_pages_and_search_index() {
+    let tmp = tempfile::tempdir().unwrap();
+    for args in [vec!["new", "Choose X"], vec!["supersede", "1", "Choose Y"]] {
+        radr_cmd()
+            .current_dir(tmp.path())
+            .args(&args)
+            .assert()
+            .success();
+    }
+
+    radr_cmd()
+        .current_dir(tmp.path())
+        .args(["site", "--out", "public"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Generated site with 6 file(s)"));
+
+    let public = tmp.path().join("public");
+    let idx = read(public.join("index.html"));
+    assert!(idx.contains("<a href=\"0001-choose-x.html\">Choose X</a>"));
+    assert!(idx.contains("id=\"status-filter\""));
+    let p2 = read(public.join("0002-choose-y.html"));
+    assert!(p2.contains("Supersedes: <a href=\"0001-choose-x.html\">"));
+    assert!(public.join("search-index.json").exists());
+    assert!(public.join("style.css").exists());
+}
+
+#[test]
+fn import_adr_tools_converts_records() {
+    let tmp = tempfile::tempdir().unwrap();
+    let legacy = tmp.path().join("doc").join("adr");
+    fs::create_dir_all(&legacy).unwrap();
+    fs::write(
+        legacy.join("0001-record-architecture-decisions.md"),
+        "# 1. Record architecture decisions\n\nDate: 2016-02-12\n\n## Status\n\nAccepted\n\n## Context\n\nWe need to record decisions.\n",
+    )
+    .unwrap();
+    fs::write(
+        legacy.join("0002-use-foo.md"),
+        "# 2. Use Foo\n\nDate: 2016-03-01\n\n## Status\n\nSuperseded by [3. Use Bar](0003-use-bar.md)\n\n## Context\n\nFoo.\n",
+    )
+    .unwrap();
+    fs::write(
+        legacy.join("0003-use-bar.md"),
+        "# 3. Use Bar\n\nDate: 2016-04-01\n\n## Status\n\nAccepted\n\nSupersedes [2. Use Foo](0002-use-foo.md)\n\n## Context\n\nBar.\n",
+    )
+    .unwrap();
+
+    radr_cmd()
+        .current_dir(tmp.path())
+        .args(["import", "adr-tools", "doc/adr"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Imported 3 ADR(s)"));
+
+    let c2 = read(adr_dir(tmp.path()).join("0002-use-foo.md"));
+    assert!(c2.starts_with("# ADR 0002: Use Foo\n\nDate: 2016-03-01\nStatus: Superseded by 0003\nSuperseded-by: 0003\n"));
+    let c3 = read(adr_dir(tmp.path()).join("0003-use-bar.md"));
+    assert!(c3.contains("Supersedes: [0002](0002-use-foo.md)"));
+
+    radr_cmd()
+        .current_dir(tmp.path())
+        .args(["lint", "--strict"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn madr_layout_keeps_metadata_in_front_matter() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join("radr.toml"), b"layout = \"madr\"\n").unwrap();
+    for args in [
+        vec!["new", "Use Postgres"],
+        vec!["supersede", "1", "Use SQLite"],
+        vec!["accept", "2"],
+        vec!["lint", "--strict"],
+    ] {
+        radr_cmd()
+            .current_dir(tmp.path())
+            .args(&args)
+            .assert()
+            .success();
+    }
+
+    let dir = adr_dir(tmp.path());
+    let old = read(dir.join("0001-use-postgres.md"));
+    assert!(old.starts_with("---\nstatus: Superseded by 0002\n"));
+    assert!(old.contains("superseded_by: 2\nhistory:\n"));
+    assert!(old.contains("\n---\n\n# Use Postgres\n"));
+    let new = read(dir.join("0002-use-sqlite.md"));
+    assert!(new.starts_with("---\nstatus: Accepted\n"));
+    assert!(new.contains("supersedes: 1\n"));
+    assert!(new.contains("## Considered Options"));
+    assert!(!new.contains("Status:"));
+
+    let idx = read(dir.join("index.md"));
+    assert!(idx.contains("[0002: Use SQLite](0002-use-sqlite.md) — Status: Accepted"));
+}
+
+#[test]
+fn full_front_matter_mode_keeps_metadata_in_yaml() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(
+        tmp.path().join("radr.yaml"),
+        b"format: mdx\nfront_matter: true\nfront_matter_mode: full\n",
+    )
+    .unwrap();
+    for args in [
+        vec!["new", "Use Hugo"],
+        vec!["accept", "1"],
+        vec!["lint", "--strict"],
+    ] {
+        radr_cmd()
+            .current_dir(tmp.path())
+            .args(&args)
+            .assert()
+            .success();
+    }
+
+    let c = read(adr_dir(tmp.path()).join("0001-use-hugo.mdx"));
+    assert!(c.starts_with("---\ntitle: Use Hugo\nnumber: 1\ndate: "));
+    assert!(c.contains("status: Accepted\n---\n\n## Context\n"));
+    assert!(!c.contains("Status:"));
+}
+
+#[test]
+fn reformat_warns_about_front_matter_keys_it_cannot_keep() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = adr_dir(tmp.path());
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("0001-pick-x.md"),
+        "---\ntitle: Pick X\nslug: /pick-x\n---\n\nDate: 2024-01-01\nStatus: Accepted\n\nBody\n",
+    )
+    .unwrap();
+
+    radr_cmd()
+        .current_dir(tmp.path())
+        .args(["reformat", "--all"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Warning: 0001: front matter key 'slug' dropped",
+        ))
+        .stdout(predicate::str::contains("Reformatted 1 ADR(s)"));
+    assert!(read(dir.join("0001-pick-x.md")).starts_with("# ADR 0001: Pick X\n"));
+}
+
+#[test]
+fn reject_keeps_status_examples_in_body() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = adr_dir(tmp.path());
+    std::fs::create_dir_all(&dir).unwrap();
+    let body = "## Decision\n\n```markdown\nStatus: Accepted\n```\n\n> Status: Accepted\n";
+    std::fs::write(
+        dir.join("0001-doc-format.md"),
+        format!(
+            "# ADR 0001: Doc Format\n\nDate: 2024-01-01\nStatus: Proposed\n\n{}",
+            body
+        ),
+    )
+    .unwrap();
+
+    radr_cmd()
+        .current_dir(tmp.path())
+        .args(["reject", "1"])
+        .assert()
+        .success();
+
+    let c = read(dir.join("0001-doc-format.md"));
+    assert!(c.contains("Status: Rejected\n\n## Decision"));
+    assert!(c.contains(&format!(
+        "{}\n## Status History\n\n- 2024-01-01: Proposed\n",
+        body
+    )));
+    let idx = read(dir.join("index.md"));
+    assert!(idx.contains("Status: Rejected"));
+}
+
+#[test]
+fn status_command_follows_configured_lifecycle() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(
+        tmp.path().join("radr.toml"),
+        b"[lifecycle]\nstatuses = [\"Proposed\", \"In Review\", \"Accepted\", \"Rejected\", \"Deprecated\"]\n\n[lifecycle.transitions]\nProposed = [\"In Review\"]\nRejected = []\n",
+    )
+    .unwrap();
+    let radr = || {
+        let mut cmd = radr_cmd();
+        cmd.current_dir(tmp.path());
+        cmd
+    };
+    radr().args(["new", "Use Kafka"]).assert().success();
+    radr()
+        .args(["accept", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "ADR 0001 cannot move from 'Proposed' to 'Accepted' (allowed: In Review)",
+        ));
+    radr()
+        .args(["status", "1", "in review"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "ADR 0001: Use Kafka is now In Review",
+        ));
+    radr().args(["reject", "Use Kafka"]).assert().success();
+    radr()
+        .args(["status", "1", "Accepted"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Use --force to override"));
+    radr().args(["accept", "1", "--force"]).assert().success();
+    radr()
+        .args(["status", "1", "Withdrawn"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown status 'Withdrawn'"));
+
+    let c = read(adr_dir(tmp.path()).join("0001-use-kafka.md"));
+    assert!(c.contains("Status: Accepted\n"));
+}
+
+#[test]
+fn status_changes_are_recorded_in_history() {
+    let tmp = tempfile::tempdir().unwrap();
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let dir = adr_dir(tmp.path());
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("0001-use-redis.md"),
+        "# ADR 0001: Use Redis\n\nDate: 2024-01-01\nStatus: Proposed\n\n## Context\n\nCaching.\n",
+    )
+    .unwrap();
+    let radr = || {
+        let mut cmd = radr_cmd();
+        cmd.current_dir(tmp.path());
+        cmd
+    };
+    radr()
+        .args(["accept", "1", "--by", "Ada Lovelace"])
+        .assert()
+        .success();
+
+    let c = read(dir.join("0001-use-redis.md"));
+    assert!(c.contains(&format!("Date: {}\nStatus: Accepted\n", today)));
+    assert!(c.ends_with(&format!(
+        "Caching.\n\n## Status History\n\n- 2024-01-01: Proposed\n- {}: Accepted (by Ada Lovelace)\n",
+        today
+    )));
+    let idx = read(dir.join("index.md"));
+    assert!(idx.contains(&format!(
+        "— Status: Accepted — Proposed: 2024-01-01 — Decided: {}",
+        today
+    )));
+
+    let out = radr().args(["--output", "json", "list"]).output().unwrap();
+    let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(v[0]["created"], "2024-01-01");
+    assert_eq!(v[0]["decided"], today.as_str());
+    assert_eq!(v[0]["history"][1]["by"], "Ada Lovelace");
+
+    // Switching to MADR moves the history into front matter
+    std::fs::write(tmp.path().join("radr.toml"), b"layout = \"madr\"\n").unwrap();
+    radr().args(["reformat", "--all"]).assert().success();
+    let c = read(dir.join("0001-use-redis.md"));
+    assert!(c.contains(&format!(
+        "history:\n- date: 2024-01-01\n  status: Proposed\n- date: {}\n  status: Accepted\n  by: Ada Lovelace\n",
+        today
+    )));
+    assert!(!c.contains("## Status History"));
+    let out = radr().args(["--output", "json", "list"]).output().unwrap();
+    let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(v[0]["created"], "2024-01-01");
+    assert_eq!(v[0]["history"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn deprecate_records_reason_and_list_filters_inactive() {
+    let tmp = tempfile::tempdir().unwrap();
+    let radr = || {
+        let mut cmd = radr_cmd();
+        cmd.current_dir(tmp.path());
+        cmd
+    };
+    radr().args(["new", "Run Our Own Queue"]).assert().success();
+    radr().args(["new", "Use Postgres"]).assert().success();
+    radr()
+        .args(["deprecate", "1", "--reason", "Moved to a managed queue"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Deprecated ADR 0001: Run Our Own Queue",
+        ));
+
+    let dir = adr_dir(tmp.path());
+    let c = read(dir.join("0001-run-our-own-queue.md"));
+    assert!(c.contains("Status: Deprecated\nDeprecation-reason: Moved to a managed queue\n"));
+    assert!(c.contains("## Status History\n"));
+    let idx = read(dir.join("index.md"));
+    assert!(idx.contains("Status: Deprecated (Moved to a managed queue)"));
+
+    radr()
+        .args(["list", "--status", "inactive"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "0001 | Run Our Own Queue | Deprecated",
+        ))
+        .stdout(predicate::str::contains("Use Postgres").not());
+    radr()
+        .args(["list", "--status", "active"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0002 | Use Postgres | Proposed"))
+        .stdout(predicate::str::contains("Run Our Own Queue").not());
+    radr().args(["lint", "--strict"]).assert().success();
+
+    // The reason survives a switch to front matter
+    std::fs::write(tmp.path().join("radr.toml"), b"layout = \"madr\"\n").unwrap();
+    radr().args(["reformat", "--all"]).assert().success();
+    let c = read(dir.join("0001-run-our-own-queue.md"));
+    assert!(c.contains("deprecation_reason: Moved to a managed queue\n"));
+    radr()
+        .args(["list", "--status", "deprecated"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "0001 | Run Our Own Queue | Deprecated",
+        ));
+}
+
+#[test]
+fn deprecation_reasons_round_trip_through_front_matter() {
+    let tmp = tempfile::tempdir().unwrap();
+    let radr = || {
+        let mut cmd = radr_cmd();
+        cmd.current_dir(tmp.path());
+        cmd
+    };
+    std::fs::write(tmp.path().join("radr.toml"), b"layout = \"madr\"\n").unwrap();
+    let reasons = [
+        "Replaced per ticket #123",
+        "[legacy] moved to vendor",
+        "- see ADR 2",
+        "#ops decided",
+        "true",
+        "null",
+    ];
+    for (i, reason) in reasons.iter().enumerate() {
+        radr()
+            .args(["new", &format!("Decision {}", i + 1)])
+            .assert()
+            .success();
+        radr()
+            .args([
+                "deprecate",
+                &(i + 1).to_string(),
+                &format!("--reason={}", reason),
+            ])
+            .assert()
+            .success();
+    }
+    let listed = || {
+        let out = radr().args(["list", "--output", "json"]).output().unwrap();
+        assert!(out.status.success());
+        let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+        v.as_array()
+            .unwrap()
+            .iter()
+            .map(|a| {
+                a["deprecation_reason"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(listed(), reasons);
+    radr().args(["lint", "--strict"]).assert().success();
+
+    // Rewriting the front matter keeps them too
+    radr().args(["reformat", "--all"]).assert().success();
+    assert_eq!(listed(), reasons);
+}
+
+#[test]
+fn supersede_replaces_several_adrs_at_once() {
+    let tmp = tempfile::tempdir().unwrap();
+    let radr = || {
+        let mut cmd = radr_cmd();
+        cmd.current_dir(tmp.path());
+        cmd
+    };
+    for title in ["Use Redis", "Use Memcached", "Cache In Process"] {
+        radr().args(["new", title]).assert().success();
+    }
+    radr()
+        .args(["supersede", "3,1,3", "Consolidate Caching"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Created ADR 0004 superseding 0001, 0003",
+        ));
+    // Nothing is created when one of the targets does not exist
+    radr()
+        .args(["supersede", "2,9", "Nope"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Could not find ADR 0009"));
+
+    let dir = adr_dir(tmp.path());
+    let new = read(dir.join("0004-consolidate-caching.md"));
+    assert!(
+        new.contains("Supersedes: [0001](0001-use-redis.md), [0003](0003-cache-in-process.md)\n")
+    );
+    for f in ["0001-use-redis.md", "0003-cache-in-process.md"] {
+        let old = read(dir.join(f));
+        assert!(old.contains("Superseded-by: 0004\n"));
+        // Listing an id twice supersedes it once
+        let entries = old
+            .lines()
+            .filter(|l| l.starts_with("- ") && l.ends_with("Superseded by 0004"));
+        assert_eq!(entries.count(), 1, "{}", old);
+    }
+    assert!(read(dir.join("0002-use-memcached.md")).contains("Status: Proposed\n"));
+    assert!(!dir.join("0005-nope.md").exists());
+    radr().args(["lint", "--strict"]).assert().success();
+
+    // Front matter keeps the list as an array, and reformat relinks every entry
+    std::fs::write(
+        tmp.path().join("radr.toml"),
+        b"format = \"mdx\"\nfront_matter = true\nfront_matter_mode = \"full\"\n",
+    )
+    .unwrap();
+    radr().args(["reformat", "--all"]).assert().success();
+    let new = read(dir.join("0004-consolidate-caching.mdx"));
+    assert!(new.contains("supersedes: [1, 3]\n"));
+    std::fs::remove_file(tmp.path().join("radr.toml")).unwrap();
+    radr().args(["reformat", "--all"]).assert().success();
+    let new = read(dir.join("0004-consolidate-caching.md"));
+    assert!(
+        new.contains("Supersedes: [0001](0001-use-redis.md), [0003](0003-cache-in-process.md)\n")
+    );
+    let out = radr().args(["--output", "json", "list"]).output().unwrap();
+    let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(v[3]["supersedes"], serde_json::json!([1, 3]));
+}
+
+#[test]
+fn link_adds_reciprocal_entries_and_unlink_removes_them() {
+    let tmp = tempfile::tempdir().unwrap();
+    let radr = || {
+        let mut cmd = radr_cmd();
+        cmd.current_dir(tmp.path());
+        cmd
+    };
+    for title in ["Use Postgres", "Add Read Replicas", "Use Sqlite For Tests"] {
+        radr().args(["new", title]).assert().success();
+    }
+    radr()
+        .args(["link", "2", "1", "--kind", "amends"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ADR 0002 amends 0001"));
+    radr()
+        .args(["link", "3", "Use Postgres", "--kind", "relates"])
+        .assert()
+        .success();
+    radr()
+        .args(["link", "1", "1", "--kind", "depends"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot link to itself"));
+
+    let dir = adr_dir(tmp.path());
+    let c = read(dir.join("0001-use-postgres.md"));
+    assert!(c.contains(
+        "Amended-by: [0002](0002-add-read-replicas.md)\nRelates-to: [0003](0003-use-sqlite-for-tests.md)\n"
+    ));
+    let c = read(dir.join("0002-add-read-replicas.md"));
+    assert!(c.contains("Amends: [0001](0001-use-postgres.md)\n"));
+    let idx = read(dir.join("index.md"));
+    assert!(idx.contains("— Amends: [0001](0001-use-postgres.md)"));
+    assert!(idx.contains("— Amended-by: [0002](0002-add-read-replicas.md)"));
+
+    radr().args(["unlink", "3", "1"]).assert().success();
+    let c = read(dir.join("0001-use-postgres.md"));
+    assert!(!c.contains("Relates-to"));
+    radr()
+        .args(["unlink", "3", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("has no typed link to 0001"));
+
+    // Links move to front matter and keep resolving
+    std::fs::write(tmp.path().join("radr.toml"), b"layout = \"madr\"\n").unwrap();
+    radr().args(["reformat", "--all"]).assert().success();
+    let c = read(dir.join("0001-use-postgres.md"));
+    assert!(c.contains("amended_by: 2\n"));
+    radr()
+        .args(["link", "3", "1", "--kind", "depends"])
+        .assert()
+        .success();
+    let c = read(dir.join("0001-use-postgres.md"));
+    assert!(c.contains("required_by: 3\n"));
+    radr().args(["lint"]).assert().success();
+
+    // A link to a removed ADR is reported as dangling
+    std::fs::remove_file(dir.join("0003-use-sqlite-for-tests.md")).unwrap();
+    radr()
+        .args(["lint"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "Required-by 0003, which does not exist",
+        ));
+}
+
+#[test]
+fn front_matter_tags_with_yaml_syntax_round_trip() {
+    let tmp = tempfile::tempdir().unwrap();
+    let radr = || {
+        let mut cmd = radr_cmd();
+        cmd.current_dir(tmp.path());
+        cmd
+    };
+    fs::write(tmp.path().join("radr.toml"), "front_matter = true\n").unwrap();
+    radr()
+        .args([
+            "new", "Bar", "--tag", "#ops", "--tag", "true", "--tag", "null",
+        ])
+        .assert()
+        .success();
+    let c = read(adr_dir(tmp.path()).join("0001-bar.md"));
+    assert!(
+        c.contains("tags: [\"#ops\", \"true\", \"null\"]\n"),
+        "{}",
+        c
+    );
+    radr().arg("lint").assert().success();
+    for tag in ["#ops", "true", "null"] {
+        radr()
+            .args(["list", "--tag", tag])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("0001 | Bar"));
+    }
+}
+
+#[test]
+fn tags_are_stored_filtered_and_grouped_in_index() {
+    let tmp = tempfile::tempdir().unwrap();
+    let radr = || {
+        let mut cmd = radr_cmd();
+        cmd.current_dir(tmp.path());
+        cmd
+    };
+    radr()
+        .args([
+            "new",
+            "Use Postgres",
+            "--tag",
+            "database",
+            "--tag",
+            "security",
+        ])
+        .assert()
+        .success();
+    radr().args(["new", "Use Rust"]).assert().success();
+    radr()
+        .args(["tag", "add", "Use Rust", "lang"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ADR 0002 tags: lang"));
+
+    let dir = adr_dir(tmp.path());
+    let c = read(dir.join("0001-use-postgres.md"));
+    assert!(c.contains("Status: Proposed\nTags: database, security\n"));
+
+    radr()
+        .args(["list", "--tag", "Database"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0001 | Use Postgres"))
+        .stdout(predicate::str::contains("Use Rust").not());
+
+    radr()
+        .args(["tag", "remove", "1", "security"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ADR 0001 tags: database"));
+    radr()
+        .args(["tag", "remove", "1", "security"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("ADR 0001 is not tagged security"));
+
+    // With front matter, tags live there and per-tag index files are written
+    std::fs::write(
+        tmp.path().join("radr.toml"),
+        b"front_matter = true\nindex_tags = \"files\"\n",
+    )
+    .unwrap();
+    radr().args(["reformat", "--all"]).assert().success();
+    let c = read(dir.join("0001-use-postgres.md"));
+    assert!(c.starts_with("---\ntitle: Use Postgres\ntags: [database]\n---\n"));
+    radr()
+        .args(["tag", "add", "1", "storage"])
+        .assert()
+        .success();
+    let c = read(dir.join("0001-use-postgres.md"));
+    assert!(c.starts_with("---\ntitle: Use Postgres\ntags: [database, storage]\n---\n"));
+    let idx = read(dir.join("index.md"));
+    assert!(idx.contains("- [lang](index-lang.md) (1)\n"));
+    let per_tag = read(dir.join("index-storage.md"));
+    assert!(per_tag.contains("[0001: Use Postgres](0001-use-postgres.md)"));
+    radr().args(["lint"]).assert().success();
+}
+
+#[test]
+fn people_are_recorded_listed_and_shown_in_index() {
+    let tmp = tempfile::tempdir().unwrap();
+    let gitconfig = tmp.path().join("gitconfig");
+    fs::write(&gitconfig, "[user]\n\tname = Ada Lovelace\n").unwrap();
+    let radr = || {
+        let mut cmd = radr_cmd();
+        cmd.current_dir(tmp.path())
+            .env("GIT_CONFIG_GLOBAL", &gitconfig);
+        cmd
+    };
+    std::fs::write(tmp.path().join("radr.toml"), b"index_deciders = true\n").unwrap();
+    radr()
+        .args(["new", "Use Postgres", "--consulted", "DBA Team"])
+        .assert()
+        .success();
+    radr()
+        .args([
+            "new",
+            "Use Rust",
+            "--author",
+            "Grace",
+            "--informed",
+            "Ops,Security",
+        ])
+        .assert()
+        .success();
+
+    let dir = adr_dir(tmp.path());
+    let c = read(dir.join("0001-use-postgres.md"));
+    assert!(c.contains("Status: Proposed\nAuthors: Ada Lovelace\nConsulted: DBA Team\n"));
+    let c = read(dir.join("0002-use-rust.md"));
+    assert!(c.contains("Authors: Grace\nInformed: Ops, Security\n"));
+
+    radr()
+        .args([
+            "accept",
+            "1",
+            "--decider",
+            "Linus",
+            "--decider",
+            "Ada Lovelace",
+        ])
+        .assert()
+        .success();
+    radr()
+        .args(["accept", "1", "--decider", "linus"])
+        .assert()
+        .success();
+    let c = read(dir.join("0001-use-postgres.md"));
+    assert!(
+        c.contains("Authors: Ada Lovelace\nDeciders: Linus, Ada Lovelace\nConsulted: DBA Team\n")
+    );
+    let idx = read(dir.join("index.md"));
+    assert!(idx.contains("— Deciders: Linus, Ada Lovelace\n"));
+
+    radr()
+        .args(["list", "--person", "linus"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0001 | Use Postgres"))
+        .stdout(predicate::str::contains("Use Rust").not());
+    radr()
+        .args(["list", "--person", "Security"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0002 | Use Rust"))
+        .stdout(predicate::str::contains("Use Postgres").not());
+
+    // MADR keeps people in front matter lists
+    std::fs::write(tmp.path().join("radr.toml"), b"layout = \"madr\"\n").unwrap();
+    radr().args(["reformat", "1"]).assert().success();
+    let c = read(dir.join("0001-use-postgres.md"));
+    assert!(c.contains("authors: [Ada Lovelace]\ndeciders: [Linus, Ada Lovelace]\nconsulted: [DBA Team]\ninformed: []\n"));
+    radr()
+        .args(["deprecate", "1", "--reason", "Moved", "--informed", "Ops"])
+        .assert()
+        .success();
+    let c = read(dir.join("0001-use-postgres.md"));
+    assert!(c.contains("informed: [Ops]\n"));
+
+    // Names that look like YAML syntax are quoted and read back as written
+    radr()
+        .args(["new", "Use Kafka", "--author", "@ops", "--decider", "null"])
+        .assert()
+        .success();
+    let c = read(dir.join("0003-use-kafka.md"));
+    assert!(
+        c.contains("authors: [\"@ops\"]\ndeciders: [\"null\"]\n"),
+        "{}",
+        c
+    );
+    radr().arg("lint").assert().success();
+    radr()
+        .args(["list", "--person", "null"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0003 | Use Kafka"));
+
+    // Superseding ADRs get the same default author as new ones
+    radr().args(["supersede", "2", "Use Go"]).assert().success();
+    let c = read(dir.join("0004-use-go.md"));
+    assert!(c.contains("authors: [Ada Lovelace]\n"), "{}", c);
+    radr()
+        .args([
+            "supersede",
+            "4",
+            "Use Zig",
+            "--author",
+            "Grace",
+            "--decider",
+            "Linus",
+        ])
+        .assert()
+        .success();
+    let c = read(dir.join("0005-use-zig.md"));
+    assert!(c.contains("authors: [Grace]\ndeciders: [Linus]\n"), "{}", c);
+}
+
+#[test]
+fn show_renders_adr_sections_and_metadata() {
+    let tmp = tempfile::tempdir().unwrap();
+    let radr = || {
+        let mut cmd = radr_cmd();
+        cmd.current_dir(tmp.path());
+        cmd
+    };
+    radr().args(["new", "Use MySQL"]).assert().success();
+    radr()
+        .args(["supersede", "1", "Use Postgres"])
+        .assert()
+        .success();
+    let path = adr_dir(tmp.path()).join("0002-use-postgres.md");
+    let c = read(&path).replace(
+        "State the decision that was made and why.",
+        "We use **Postgres**:\n\n- managed\n- replicated",
+    );
+    fs::write(&path, c).unwrap();
+
+    // Not a terminal here, so no pager and no styling
+    radr()
+        .args(["show", "2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "0002: Use Postgres\n==================\nStatus: Proposed\n",
+        ))
+        .stdout(predicate::str::contains("Supersedes: 0001 Use MySQL\n"))
+        .stdout(predicate::str::contains(
+            "Decision\n--------\n\nWe use Postgres:\n\n• managed\n• replicated\n",
+        ))
+        .stdout(predicate::str::contains("\x1b[").not());
+    radr()
+        .args(["show", "use postgres", "--section", "decision"])
+        .assert()
+        .success()
+        .stdout("We use Postgres:\n\n• managed\n• replicated\n");
+    radr()
+        .args(["show", "2", "--section", "Risks"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "has no section 'Risks' (sections: Context, Decision, Consequences)",
+        ));
+    radr()
+        .args(["--output", "json", "show", "2", "--section", "decision"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"section\": \"Decision\""))
+        .stdout(predicate::str::contains(
+            "\"content\": \"We use **Postgres**:\\n\\n- managed\\n- replicated\\n\"",
+        ));
+    radr()
+        .args(["show", "1", "--meta"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("number: 1\ntitle: Use MySQL\n"))
+        .stdout(predicate::str::contains("superseded_by: 2\n"));
+    radr()
+        .args(["show", "9"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("ADR not found"));
+}
+
+#[cfg(unix)]
+#[test]
+fn edit_opens_editor_and_validates_result() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let script = |name: &str, body: &str| {
+        let path = tmp.path().join(name);
+        fs::write(&path, format!("#!/bin/sh\n{}\n", body)).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    };
+    let append = script("append.sh", "printf 'Edited body.\\n' >> \"$1\"");
+    let retitle = script(
+        "retitle.sh",
+        "sed -i.bak 's/^# ADR 0001: Use Postgres$/# ADR 0001: Use Managed Postgres/' \"$1\"",
+    );
+    let breaks = script(
+        "break.sh",
+        "sed -i.bak -e '/^Status:/d' -e 's/^Date: .*/&\\nSupersedes: the old one/' \"$1\"",
+    );
+    let radr = |editor: &Path| {
+        let mut cmd = radr_cmd();
+        cmd.current_dir(tmp.path())
+            .env("EDITOR", editor)
+            .env_remove("VISUAL");
+        cmd
+    };
+
+    radr(&append)
+        .args(["new", "Use Postgres", "--edit"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Created ADR 0001: Use Postgres"));
+    let path = adr_dir(tmp.path()).join("0001-use-postgres.md");
+    assert!(read(&path).ends_with("Edited body.\n"));
+
+    // VISUAL wins over EDITOR; the index follows the new title
+    radr(&append)
+        .env("VISUAL", &retitle)
+        .args(["edit", "use postgres"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Edited ADR 0001: Use Managed Postgres",
+        ))
+        .stderr(predicate::str::contains("title-mismatch"));
+    assert!(read(adr_dir(tmp.path()).join("index.md")).contains("Use Managed Postgres"));
+
+    // Without a terminal to ask on, errors fail the command instead of reopening
+    radr(&breaks)
+        .args(["edit", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("error[missing-status]"))
+        .stderr(predicate::str::contains(
+            "error[malformed-field] Supersedes item 'the old one' is not an ADR reference",
+        ))
+        .stderr(predicate::str::contains(
+            "0001-use-postgres.md has 2 error(s) after editing",
+        ));
+
+    radr(Path::new("false"))
+        .args(["edit", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Editor 'false' exited with"));
+}
+
+#[test]
+fn init_sets_up_repository_and_other_commands_require_it() {
+    let tmp = tempfile::tempdir().unwrap();
+    let radr = || {
+        let mut cmd = radr_cmd();
+        cmd.current_dir(tmp.path());
+        cmd
+    };
+    radr()
+        .args(["list"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("run `radr init`"));
+    assert!(!tmp.path().join("docs").exists());
+
+    radr()
+        .args([
+            "init",
+            "--dir",
+            "decisions",
+            "--format",
+            "mdx",
+            "--front-matter",
+            "--config-format",
+            "yaml",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Wrote radr.yaml"))
+        .stdout(predicate::str::contains(
+            "Created ADR 0001: Record architecture decisions at decisions/0001-record-architecture-decisions.mdx",
+        ));
+    let config = read(tmp.path().join("radr.yaml"));
+    assert!(config.contains("\nadr_dir: \"decisions\"\n"));
+    assert!(config.contains("\nformat: \"mdx\"\n"));
+    assert!(config.contains("# layout: \"classic\"\n"));
+    let first = read(
+        tmp.path()
+            .join("decisions/0001-record-architecture-decisions.mdx"),
+    );
+    assert!(first.starts_with("---\ntitle: Record architecture decisions\n---\n"));
+    assert!(first.contains("Status: Accepted\n"));
+    assert!(read(tmp.path().join("decisions/index.md")).contains("Record architecture decisions"));
+
+    // The new config is picked up by later commands
+    radr()
+        .args(["list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "0001 | Record architecture decisions",
+        ));
+    radr().args(["lint"]).assert().success();
+
+    radr()
+        .args(["init"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("radr is already set up here"));
+    radr()
+        .args([
+            "--config",
+            "radr.json",
+            "init",
+            "--dir",
+            "decisions",
+            "--force",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Wrote radr.json"))
+        .stdout(predicate::str::contains("Created ADR").not());
+    assert_eq!(
+        read(tmp.path().join("radr.json")),
+        "{\n  \"adr_dir\": \"decisions\",\n  \"format\": \"md\",\n  \"front_matter\": false\n}\n"
+    );
+}
+
+#[test]
+fn config_is_found_in_parent_directories_up_to_repository_root() {
+    let tmp = tempfile::tempdir().unwrap();
+    let repo = tmp.path().join("repo");
+    let src = repo.join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::create_dir_all(repo.join(".git")).unwrap();
+    fs::write(
+        repo.join("radr.toml"),
+        "adr_dir = \"decisions\"\ntemplate = \"tpl.md\"\n",
+    )
+    .unwrap();
+    fs::write(
+        repo.join("tpl.md"),
+        "# ADR {{NUMBER}}: {{TITLE}}\n\nDate: {{DATE}}\nStatus: {{STATUS}}\n\nFrom template.\n",
+    )
+    .unwrap();
+    let radr = |dir: &Path| {
+        let mut cmd = radr_cmd();
+        cmd.current_dir(dir);
+        cmd
+    };
+
+    radr(&src)
+        .args(["config", "path"])
+        .assert()
+        .success()
+        .stdout("../radr.toml\n");
+    radr(&src)
+        .args(["new", "Use Postgres"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "at ../decisions/0001-use-postgres.md",
+        ));
+    assert!(!src.join("decisions").exists());
+    assert!(read(repo.join("decisions/0001-use-postgres.md")).contains("From template."));
+    radr(&src)
+        .args(["list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0001 | Use Postgres"));
+
+    // The search stops at the repository root
+    let nested = repo.join("vendor").join("lib");
+    fs::create_dir_all(nested.join(".git")).unwrap();
+    radr(&nested)
+        .args(["config", "path"])
+        .assert()
+        .success()
+        .stdout("No config file found; using defaults\n");
+    radr(&nested)
+        .args(["--output", "json", "config", "path"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"path\": null"));
+}
+
+#[test]
+fn config_validate_reports_problems_and_blocks_other_commands() {
+    let tmp = tempfile::tempdir().unwrap();
+    let radr = || {
+        let mut cmd = radr_cmd();
+        cmd.current_dir(tmp.path());
+        cmd
+    };
+    fs::write(
+        tmp.path().join("radr.toml"),
+        "adr_directory = \"adrs\"\nformat = \"rst\"\n",
+    )
+    .unwrap();
+    radr()
+        .args(["config", "validate"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "radr.toml: unknown key 'adr_directory' (did you mean 'adr_dir'?)\n",
+        ))
+        .stdout(predicate::str::contains(
+            "radr.toml: format must be one of md, mdx, not 'rst'\n",
+        ))
+        .stderr(predicate::str::contains("Config has 2 problem(s)"));
+    radr()
+        .args(["new", "Use Postgres"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid config at radr.toml"));
+    assert!(!adr_dir(tmp.path()).exists());
+
+    fs::write(tmp.path().join("radr.toml"), "adr_dir = \"adrs\"\n").unwrap();
+    radr()
+        .args(["config", "validate"])
+        .assert()
+        .success()
+        .stdout("radr.toml is valid\n");
+    radr()
+        .args(["config", "schema"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"title\": \"radr config\""));
+}
+
+#[test]
+fn config_layers_user_project_env_and_set() {
+    let tmp = tempfile::tempdir().unwrap();
+    let xdg = tmp.path().join("xdg");
+    let project = tmp.path().join("project");
+    fs::create_dir_all(xdg.join("radr").join("templates")).unwrap();
+    fs::create_dir_all(&project).unwrap();
+    fs::write(
+        xdg.join("radr").join("templates").join("adr.md"),
+        "# ADR {{NUMBER}}: {{TITLE}}\n\nBy {{AUTHORS}}\n",
+    )
+    .unwrap();
+    fs::write(
+        xdg.join("radr").join("config.toml"),
+        "author = \"Ada Lovelace\"\ntemplate = \"templates/adr.md\"\nformat = \"mdx\"\n",
+    )
+    .unwrap();
+    fs::write(
+        project.join("radr.toml"),
+        "adr_dir = \"adrs\"\nformat = \"md\"\n",
+    )
+    .unwrap();
+    let radr = || {
+        let mut cmd = radr_cmd();
+        cmd.current_dir(&project).env("XDG_CONFIG_HOME", &xdg);
+        cmd
+    };
+
+    radr()
+        .args(["config", "show", "--origin"])
+        .env("RADR_FRONT_MATTER", "yes")
+        .args(["--set", "index_name=ADRS.md"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r#"(?m)^adr_dir = "adrs" +# radr\.toml$"#).unwrap())
+        .stdout(predicate::str::is_match(r#"(?m)^format = "md" +# radr\.toml$"#).unwrap())
+        .stdout(
+            predicate::str::is_match(r#"(?m)^author = "Ada Lovelace" +# .*config\.toml$"#).unwrap(),
+        )
+        .stdout(
+            predicate::str::is_match(r"(?m)^front_matter = true +# RADR_FRONT_MATTER$").unwrap(),
+        )
+        .stdout(
+            predicate::str::is_match(
+                r#"(?m)^index_name = "ADRS\.md" +# --set index_name=ADRS\.md$"#,
+            )
+            .unwrap(),
+        )
+        .stdout(predicate::str::is_match(r"(?m)^index_graph = false +# default$").unwrap());
+    radr()
+        .args(["--output", "json", "config", "show"])
+        .env("RADR_FORMAT", "mdx")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"format\": \"mdx\""));
+
+    // The user config supplies the author and the template
+    fs::create_dir_all(project.join("adrs")).unwrap();
+    radr().args(["new", "Use Postgres"]).assert().success();
+    let adr = fs::read_to_string(project.join("adrs").join("0001-use-postgres.md")).unwrap();
+    assert!(adr.contains("By Ada Lovelace"), "{}", adr);
+
+    radr()
+        .args(["config", "validate", "--set", "formt=mdx"])
+        .env("RADR_LAYOUT", "nygard")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "RADR_LAYOUT: layout must be one of classic, madr, not 'nygard'\n",
+        ))
+        .stdout(predicate::str::contains(
+            "--set formt=mdx: unknown key 'formt' (did you mean 'format'?)\n",
+        ));
+    radr()
+        .args(["list", "--set", "front_matter=maybe"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid config at --set front_matter=maybe:\n  - front_matter must be true or false, not 'maybe'",
+        ));
+}
+
+#[test]
+fn config_set_edits_the_file_and_offers_reformat() {
+    let tmp = tempfile::tempdir().unwrap();
+    let radr = || {
+        let mut cmd = radr_cmd();
+        cmd.current_dir(tmp.path());
+        cmd
+    };
+    radr()
+        .args(["config", "set", "format", "mdx"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No config file found"));
+    radr().args(["init", "--no-first-adr"]).assert().success();
+    radr().args(["new", "Use Postgres"]).assert().success();
+
+    // Without a terminal, the reformat is only suggested
+    radr()
+        .args(["config", "set", "format", "mdx"])
+        .assert()
+        .success()
+        .stdout("Set format = \"mdx\" in radr.toml\n")
+        .stderr(predicate::str::contains(
+            "Run `radr reformat --all` to update the 1 existing ADR(s)",
+        ));
+    let toml = fs::read_to_string(tmp.path().join("radr.toml")).unwrap();
+    assert!(toml.starts_with("# radr configuration."));
+    assert!(toml.contains("\nformat = \"mdx\"\n"));
+    assert!(adr_dir(tmp.path()).join("0001-use-postgres.md").exists());
+    radr()
+        .args(["config", "get", "format"])
+        .assert()
+        .success()
+        .stdout("mdx\n");
+
+    radr()
+        .args([
+            "--output",
+            "json",
+            "config",
+            "set",
+            "front_matter",
+            "yes",
+            "--reformat",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"reformatted\": 1"));
+    assert!(adr_dir(tmp.path()).join("0001-use-postgres.mdx").exists());
+    radr()
+        .args(["--output", "json", "config", "get", "front_matter"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"value\": true"))
+        .stdout(predicate::str::contains("\"origin\": \"radr.toml\""));
+
+    // Settings that would break the config are refused
+    radr()
+        .args(["config", "set", "layout", "nygard"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "layout must be one of classic, madr, not 'nygard'",
+        ));
+    radr()
+        .args(["config", "set", "index_name", "0001-index.md"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("it was left unchanged"));
+    radr()
+        .args(["config", "get", "index_name"])
+        .assert()
+        .success()
+        .stdout("index.md\n");
+}